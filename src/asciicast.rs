@@ -0,0 +1,222 @@
+use crate::clock;
+use crate::input::{self, Interrupt};
+use crate::output;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Pause between lines when replaying a plain-text transcript
+const TRANSCRIPT_LINE_DELAY: u64 = 60;
+
+/// Longest pause replayed between two events; longer idle stretches are cut short
+const MAX_IDLE: Duration = Duration::from_secs(5);
+
+/// How long playback sleeps before looking at the keyboard again
+const WAIT_SLICE: Duration = Duration::from_millis(50);
+
+/// First line of an asciicast v2 file
+#[derive(Serialize)]
+struct Header {
+    version: u8,
+    width: u16,
+    height: u16,
+    timestamp: u64,
+    title: &'static str,
+    env: BTreeMap<&'static str, String>,
+}
+
+/// Writes terminal output as an asciicast v2 recording
+pub struct Recorder {
+    writer: BufWriter<File>,
+    started: Instant,
+}
+
+impl Recorder {
+    /// Create a recording file and write the asciicast header
+    pub fn create(path: &Path, width: u16, height: u16) -> io::Result<Self> {
+        let file = File::create(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        let mut writer = BufWriter::new(file);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let env = ["SHELL", "TERM"]
+            .into_iter()
+            .map(|key| (key, std::env::var(key).unwrap_or_default()))
+            .collect();
+        let header = Header {
+            version: 2,
+            width,
+            height,
+            timestamp,
+            title: "install-nothing",
+            env,
+        };
+        writeln!(writer, "{}", serde_json::to_string(&header)?)?;
+        writer.flush()?;
+
+        Ok(Self {
+            writer,
+            started: Instant::now(),
+        })
+    }
+
+    /// Append an output event stamped with the time since recording started
+    pub fn record(&mut self, data: &str) -> io::Result<()> {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        // The terminal turns "\n" into "\r\n"; players expect the translated form
+        let data = output::to_crlf(data);
+        writeln!(self.writer, "{}", json!([elapsed, "o", data]))?;
+        self.writer.flush()
+    }
+}
+
+/// Play back an asciicast v2 recording, or a plain-text transcript line by line
+pub fn play(path: &Path, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
+    let file = File::open(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let first = match lines.next() {
+        Some(line) => line?,
+        None => return Ok(()),
+    };
+
+    let is_asciicast = serde_json::from_str::<serde_json::Value>(&first)
+        .map(|header| header["version"] == 2)
+        .unwrap_or(false);
+
+    if !is_asciicast {
+        output::write_str(&output::to_crlf(&format!("{}\n", first)))?;
+        for line in lines {
+            if control(exit_check)? == Control::Stop {
+                return Ok(());
+            }
+            output::write_str(&output::to_crlf(&format!("{}\n", line?)))?;
+            output::flush()?;
+            clock::sleep(Duration::from_millis(TRANSCRIPT_LINE_DELAY));
+        }
+        return Ok(());
+    }
+
+    let mut last_time = 0.0;
+    for (index, line) in lines.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (time, kind, data) = parse_event(&line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}:{}: invalid event: {}", path.display(), index + 2, e),
+            )
+        })?;
+        if kind != "o" {
+            continue;
+        }
+
+        if time > last_time {
+            let gap = Duration::try_from_secs_f64(time - last_time).unwrap_or(MAX_IDLE);
+            last_time = time;
+            if wait(gap.min(MAX_IDLE), exit_check)? == Control::Stop {
+                return Ok(());
+            }
+        }
+        if control(exit_check)? == Control::Stop {
+            return Ok(());
+        }
+
+        output::write_str(&data)?;
+        output::flush()?;
+    }
+
+    Ok(())
+}
+
+/// Split an event line into its time, kind and data
+fn parse_event(line: &str) -> serde_json::Result<(f64, String, String)> {
+    serde_json::from_str(line)
+}
+
+/// What the keyboard asks of playback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    Play,
+    /// Jump over the rest of the current pause
+    Skip,
+    /// End playback as if the recording was over
+    Stop,
+}
+
+/// Act on a pending interrupt: `q` stops playback, `s` skips ahead to the next output,
+/// `f` has nothing to fail and is ignored, and Ctrl+C cancels
+fn control(exit_check: &dyn Fn() -> bool) -> io::Result<Control> {
+    if !exit_check() {
+        return Ok(Control::Play);
+    }
+    match input::take_interrupt() {
+        Some(Interrupt::Quit) => Ok(Control::Stop),
+        Some(Interrupt::Skip) => Ok(Control::Skip),
+        Some(Interrupt::Fail) => Ok(Control::Play),
+        Some(Interrupt::Cancel) | None => {
+            Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"))
+        }
+    }
+}
+
+/// Sit out the gap between two events in short slices, so the keyboard controls keep
+/// working and a speed change applies straight away
+fn wait(gap: Duration, exit_check: &dyn Fn() -> bool) -> io::Result<Control> {
+    let mut left = gap;
+    loop {
+        match control(exit_check)? {
+            Control::Play => {}
+            Control::Skip => return Ok(Control::Play),
+            Control::Stop => return Ok(Control::Stop),
+        }
+        let slice = clock::scale(left).min(WAIT_SLICE);
+        if slice.is_zero() {
+            return Ok(Control::Play);
+        }
+        thread::sleep(slice);
+        left = left.saturating_sub(slice.mul_f64(clock::speed()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_reads_back() {
+        let path =
+            std::env::temp_dir().join(format!("install-nothing-{}.cast", std::process::id()));
+        let mut recorder = Recorder::create(&path, 100, 30).unwrap();
+        recorder.record("one\ntwo\r\n").unwrap();
+        drop(recorder);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let mut lines = content.lines();
+
+        let header: serde_json::Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+        assert_eq!(header["version"], 2);
+        assert_eq!(header["width"], 100);
+        assert_eq!(header["height"], 30);
+        assert_eq!(header["title"], "install-nothing");
+
+        let (time, kind, data) = parse_event(lines.next().unwrap()).unwrap();
+        assert!(time >= 0.0);
+        assert_eq!(kind, "o");
+        assert_eq!(data, "one\r\ntwo\r\n");
+        assert!(lines.next().is_none());
+    }
+}
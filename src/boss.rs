@@ -0,0 +1,226 @@
+use crate::config::{BossConfig, BossView};
+use chrono::Local;
+use crossterm::{cursor, event::KeyCode, queue, style::Print, terminal};
+use rand::Rng;
+use std::io::{self, Write};
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+use sysinfo::System;
+
+/// A row of the fake `top`: (pid, user, virt, res, shr, command)
+type Process = (u32, &'static str, u32, u32, u32, &'static str);
+
+const PROCESSES: &[Process] = &[
+    (1, "root", 167_744, 13_120, 8_392, "systemd"),
+    (412, "root", 48_236, 9_872, 7_540, "systemd-journal"),
+    (688, "systemd+", 25_532, 12_960, 8_708, "systemd-resolve"),
+    (731, "root", 238_092, 7_904, 6_820, "accounts-daemon"),
+    (744, "root", 9_496, 2_784, 2_516, "cron"),
+    (749, "message+", 9_936, 5_820, 4_144, "dbus-daemon"),
+    (802, "syslog", 222_404, 5_640, 4_296, "rsyslogd"),
+    (911, "root", 15_432, 9_136, 7_648, "sshd"),
+    (1204, "user", 17_380, 9_960, 8_072, "systemd"),
+    (1311, "user", 11_284, 5_272, 3_448, "bash"),
+    (1877, "user", 1_128_936, 210_452, 98_204, "code"),
+    (2034, "user", 2_563_120, 389_844, 142_096, "firefox"),
+    (2291, "user", 11_548, 4_208, 3_364, "top"),
+    (2370, "root", 0, 0, 0, "kworker/2:1-events"),
+    (2411, "root", 0, 0, 0, "kworker/u8:3-flush-8:0"),
+];
+
+/// How the boss key is configured, set once from the config file
+static CONFIG: LazyLock<RwLock<BossConfig>> = LazyLock::new(|| RwLock::new(BossConfig::default()));
+
+/// Use `config` for every boss screen from now on
+pub fn configure(config: BossConfig) {
+    *CONFIG.write().unwrap() = config;
+}
+
+/// A calm screen that stands in for the installer until the boss key is pressed again
+pub struct BossScreen {
+    config: BossConfig,
+    /// What has been typed at the shell prompt so far
+    typed: String,
+}
+
+impl BossScreen {
+    pub fn new() -> Self {
+        Self {
+            config: CONFIG.read().unwrap().clone(),
+            typed: String::new(),
+        }
+    }
+
+    /// How often the screen wants redrawing, if it changes by itself at all
+    pub fn refresh(&self) -> Option<Duration> {
+        match self.config.view {
+            BossView::Top => Some(Duration::from_secs(3)),
+            BossView::Shell => None,
+        }
+    }
+
+    /// Draw the screen from scratch
+    pub fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        match self.config.view {
+            BossView::Top => {
+                let (width, rows) = terminal::size().unwrap_or((80, 24));
+                queue!(out, cursor::Hide, cursor::MoveTo(0, 0))?;
+                for line in top(rows as usize) {
+                    let line: String = line.chars().take(width as usize).collect();
+                    queue!(
+                        out,
+                        Print(line),
+                        terminal::Clear(terminal::ClearType::UntilNewLine),
+                        Print("\r\n")
+                    )?;
+                }
+                queue!(out, terminal::Clear(terminal::ClearType::FromCursorDown))?;
+            }
+            BossView::Shell => {
+                queue!(
+                    out,
+                    cursor::MoveTo(0, 0),
+                    cursor::Show,
+                    Print(format!(
+                        "Last login: {} from 10.0.4.17\r\n",
+                        Local::now().format("%a %b %e %H:%M:%S %Y")
+                    )),
+                    Print(&self.config.prompt),
+                    Print(&self.typed)
+                )?;
+            }
+        }
+        out.flush()
+    }
+
+    /// Whether `key` takes the screen down again. The shell view types `b` like any
+    /// other letter, so there only keys that don't type anything leave
+    pub fn leaves_on(&self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Esc | KeyCode::F(12) => true,
+            KeyCode::Char('b') => self.config.view == BossView::Top,
+            _ => false,
+        }
+    }
+
+    /// Let the shell view echo typing, so it holds up to a glance at the keyboard
+    pub fn key(&mut self, key: KeyCode, out: &mut impl Write) -> io::Result<()> {
+        if self.config.view != BossView::Shell {
+            return Ok(());
+        }
+        match key {
+            KeyCode::Char(c) => {
+                self.typed.push(c);
+                queue!(out, Print(c))?;
+            }
+            KeyCode::Backspace if self.typed.pop().is_some() => {
+                queue!(out, Print("\x08 \x08"))?;
+            }
+            KeyCode::Enter => {
+                self.typed.clear();
+                queue!(out, Print("\r\n"), Print(&self.config.prompt))?;
+            }
+            _ => {}
+        }
+        out.flush()
+    }
+}
+
+impl Default for BossScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One refresh of a machine with nothing much going on
+fn top(rows: usize) -> Vec<String> {
+    let mut rng = rand::thread_rng();
+    let uptime = System::uptime();
+    let load: Vec<f32> = (0..3).map(|_| rng.gen_range(0.0..0.2)).collect();
+    let user = rng.gen_range(0.3..1.5);
+    let system = rng.gen_range(0.1..0.6);
+    let mut lines = vec![
+        format!(
+            "top - {} up {} days, {:2}:{:02},  1 user,  load average: {:.2}, {:.2}, {:.2}",
+            Local::now().format("%H:%M:%S"),
+            uptime / 86_400,
+            uptime / 3600 % 24,
+            uptime / 60 % 60,
+            load[0],
+            load[1],
+            load[2]
+        ),
+        format!(
+            "Tasks: {} total,   1 running, {} sleeping,   0 stopped,   0 zombie",
+            PROCESSES.len() + 196,
+            PROCESSES.len() + 195
+        ),
+        format!(
+            "%Cpu(s): {:4.1} us, {:4.1} sy,  0.0 ni, {:4.1} id,  0.0 wa,  0.0 hi,  0.0 si,  0.0 st",
+            user,
+            system,
+            100.0 - user - system
+        ),
+        "MiB Mem :  15890.4 total,   9231.7 free,   3102.5 used,   3556.2 buff/cache".to_string(),
+        "MiB Swap:   2048.0 total,   2048.0 free,      0.0 used.  12402.8 avail Mem".to_string(),
+        String::new(),
+        "    PID USER      PR  NI    VIRT    RES    SHR S  %CPU  %MEM     TIME+ COMMAND"
+            .to_string(),
+    ];
+
+    let mut processes: Vec<(f32, &Process)> = PROCESSES
+        .iter()
+        .map(|process| {
+            let cpu = if rng.gen_bool(0.3) {
+                rng.gen_range(0.3..2.0)
+            } else {
+                0.0
+            };
+            (cpu, process)
+        })
+        .collect();
+    processes.sort_by(|a, b| b.0.total_cmp(&a.0));
+    for (cpu, &(pid, user, virt, res, shr, command)) in processes {
+        let running = if command == "top" { "R" } else { "S" };
+        let minutes = pid as u64 * 7 % 600;
+        lines.push(format!(
+            "{:>7} {:<9} 20   0 {:>7} {:>6} {:>6} {} {:5.1} {:5.1} {:>3}:{:02}.{:02} {}",
+            pid,
+            user,
+            virt,
+            res,
+            shr,
+            running,
+            cpu,
+            res as f32 / 16_271_769.0 * 100.0,
+            minutes / 60,
+            minutes % 60,
+            pid % 100,
+            command
+        ));
+    }
+    lines.truncate(rows.saturating_sub(1));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_fits_the_screen() {
+        assert_eq!(top(5).len(), 4);
+        assert!(top(100).len() <= 100);
+    }
+
+    #[test]
+    fn test_shell_types_b_instead_of_leaving() {
+        let mut screen = BossScreen::new();
+        screen.config.view = BossView::Top;
+        assert!(screen.leaves_on(KeyCode::Char('b')));
+        screen.config.view = BossView::Shell;
+        assert!(!screen.leaves_on(KeyCode::Char('b')));
+        assert!(screen.leaves_on(KeyCode::Esc));
+        assert!(screen.leaves_on(KeyCode::F(12)));
+    }
+}
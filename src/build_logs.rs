@@ -25,7 +25,7 @@ impl BuildLogs {
 
     #[allow(dead_code)]
     pub fn random_batch(&self, count: usize) -> Vec<&str> {
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
         let mut batch = Vec::new();
 
         for _ in 0..count {
@@ -1,76 +1,13 @@
-use clap::{Parser, ValueEnum};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
-pub enum Stage {
-    /// BIOS initialization
-    Bios,
-    /// Boot sequence
-    Boot,
-    /// Bootloader installation
-    Bootloader,
-    /// Filesystem setup
-    Filesystem,
-    /// System installation
-    System,
-    /// Network configuration
-    Network,
-    /// Driver installation
-    Drivers,
-    /// Initramfs generation
-    Initramfs,
-    /// Package installation
-    Packages,
-    /// Linux kernel compilation
-    Kernel,
-    /// Compilation
-    Compilation,
-    /// Deno runtime compilation
-    Deno,
-    /// Database setup
-    Database,
-    /// X.org configuration
-    Xorg,
-    /// Services configuration
-    Services,
-    /// Retro software installation
-    Retro,
-    /// Locale configuration
-    Locale,
-    /// Container orchestration
-    Container,
-    /// AI model loading
-    Ai,
-    /// Cloud provisioning
-    Cloud,
-}
-
-impl Stage {
-    /// Returns all stages in installation order
-    pub fn all() -> Vec<Stage> {
-        vec![
-            Stage::Bios,
-            Stage::Boot,
-            Stage::Bootloader,
-            Stage::Filesystem,
-            Stage::System,
-            Stage::Network,
-            Stage::Drivers,
-            Stage::Initramfs,
-            Stage::Packages,
-            Stage::Kernel,
-            Stage::Compilation,
-            Stage::Deno,
-            Stage::Database,
-            Stage::Xorg,
-            Stage::Services,
-            Stage::Retro,
-            Stage::Locale,
-            Stage::Container,
-            Stage::Ai,
-            Stage::Cloud,
-        ]
-    }
-}
+use crate::clock;
+use crate::crt;
+use crate::ordering::{self, StageOrder};
+use crate::serial;
+use crate::stages::{stage_overview, Stage};
+use crate::theme::ThemeName;
+use crate::ui::{SpinnerStyle, UnattendedPolicy};
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -78,106 +15,282 @@ impl Stage {
     version,
     about = "A nostalgic infinite installer simulator",
     long_about = "Universal System Installer - simulates an endless installation process.\n\
-                  Select which stages to run, or use --all to run everything (default)."
+                  Select which stages to run, or use --all to run everything (default).",
+    after_long_help = stage_overview()
 )]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Stages to install (defaults to all if none specified)
-    #[arg(value_enum)]
-    pub stages: Vec<Stage>,
+    #[arg(value_name = "STAGE")]
+    pub stages: Vec<String>,
 
     /// Install all stages (default behavior)
     #[arg(short, long, conflicts_with = "stages")]
     pub all: bool,
 
     /// Exclude specific stages from installation
-    #[arg(short, long, value_enum, num_args = 0.., conflicts_with = "stages")]
-    pub exclude: Vec<Stage>,
+    #[arg(short, long, value_name = "STAGE", num_args = 0.., conflicts_with = "stages")]
+    pub exclude: Vec<String>,
+
+    /// How stages are ordered within each installation cycle
+    #[arg(long, value_enum, default_value_t = StageOrder::Shuffle)]
+    pub order: StageOrder,
+
+    /// Relative weight of a stage for --order weighted (e.g. kernel=3, cloud=0)
+    #[arg(long, value_name = "STAGE=N", value_parser = ordering::parse_weight)]
+    pub weight: Vec<(String, u32)>,
+
+    /// Stop after this many installation cycles
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub cycles: Option<u32>,
+
+    /// Stop after running for this long (e.g. 90s, 25m, 1h30m)
+    #[arg(long, value_name = "DURATION", value_parser = clock::parse_duration)]
+    pub duration: Option<Duration>,
+
+    /// Seed the random number generator to replay an identical session
+    #[arg(long, value_name = "SEED")]
+    pub seed: Option<u64>,
+
+    /// Load simulation settings from a TOML file
+    /// (defaults to $XDG_CONFIG_HOME/install-nothing/config.toml when present)
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Load an extra stage from a TOML script (stages in
+    /// $XDG_CONFIG_HOME/install-nothing/stages are loaded automatically)
+    #[arg(long, value_name = "PATH")]
+    pub stage_file: Vec<PathBuf>,
+
+    /// Speed multiplier for all delays (e.g. 0.25, 10x, or 'instant')
+    #[arg(
+        long,
+        global = true,
+        value_name = "MULTIPLIER",
+        default_value = "1",
+        value_parser = clock::parse_speed
+    )]
+    pub speed: f64,
+
+    /// Plain, pipe-friendly output without colors or in-place redraws
+    /// (enabled automatically when stdout is not a terminal)
+    #[arg(long, global = true)]
+    pub plain: bool,
+
+    /// Color theme for all output
+    #[arg(long, global = true, value_enum, default_value_t = ThemeName::Default)]
+    pub theme: ThemeName,
+
+    /// Spinner style for every spinner, overriding stage and theme choices
+    #[arg(long, global = true, value_enum, value_name = "STYLE")]
+    pub spinner: Option<SpinnerStyle>,
+
+    /// Pace all output like a serial terminal at this baud rate (300, 1200, 2400 or 9600)
+    #[arg(long, global = true, value_name = "RATE", value_parser = serial::parse_baud)]
+    pub baud: Option<u32>,
+
+    /// Garble characters on the --baud line with this chance each (default 0.02)
+    #[arg(
+        long,
+        global = true,
+        value_name = "CHANCE",
+        num_args = 0..=1,
+        default_missing_value = "0.02",
+        requires = "baud",
+        value_parser = serial::parse_noise
+    )]
+    pub line_noise: Option<f64>,
+
+    /// Add CRT glitches (flicker, tearing, burn-in, screen roll) at this intensity from 0 to 1
+    /// (default 0.3)
+    #[arg(
+        long,
+        global = true,
+        value_name = "INTENSITY",
+        num_args = 0..=1,
+        default_missing_value = "0.3",
+        value_parser = crt::parse_intensity
+    )]
+    pub crt: Option<f64>,
+
+    /// Run in a full-screen dashboard with a progress gauge and log pane
+    #[arg(long, conflicts_with = "plain")]
+    pub dashboard: bool,
+
+    /// Keep a status line with the cycle, stage, elapsed time and ETA at the bottom of the screen
+    #[arg(long, conflicts_with_all = ["plain", "dashboard"])]
+    pub status_line: bool,
+
+    /// Make the license agreement be read and accepted before the first cycle
+    #[arg(long)]
+    pub eula: bool,
+
+    /// Answer prompts automatically: take the defaults, say yes to everything, or pick at random
+    #[arg(
+        long,
+        value_enum,
+        value_name = "POLICY",
+        num_args = 0..=1,
+        default_missing_value = "defaults"
+    )]
+    pub unattended: Option<UnattendedPolicy>,
+
+    /// Record the session to an asciicast v2 file
+    #[arg(long, value_name = "PATH")]
+    pub record: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Replay a recorded session (asciicast v2 or a plain transcript)
+    Replay {
+        /// Recording to play back
+        path: PathBuf,
+    },
 }
 
 impl Cli {
     /// Returns the selected stages, defaulting to all if none specified
-    pub fn get_stages(&self) -> Vec<Stage> {
+    pub fn get_stages(&self) -> Result<Vec<Stage>, String> {
         let mut stages = if self.all || self.stages.is_empty() {
             Stage::all()
         } else {
-            self.stages.clone()
+            self.stages
+                .iter()
+                .map(|id| resolve(id))
+                .collect::<Result<_, _>>()?
         };
 
         if !self.exclude.is_empty() {
-            stages.retain(|stage| !self.exclude.contains(stage));
+            let excluded = self
+                .exclude
+                .iter()
+                .map(|id| resolve(id))
+                .collect::<Result<Vec<_>, _>>()?;
+            stages.retain(|stage| !excluded.contains(stage));
         }
 
-        stages
+        Ok(stages)
+    }
+
+    /// Returns the --weight values with their stages resolved, checking that they
+    /// apply and leave at least one of `stages` able to be drawn
+    pub fn get_weights(&self, stages: &[Stage]) -> Result<Vec<(Stage, u32)>, String> {
+        if self.weight.is_empty() {
+            return Ok(Vec::new());
+        }
+        if self.order != StageOrder::Weighted {
+            return Err("--weight only applies with --order weighted".to_string());
+        }
+
+        let weights: Vec<(Stage, u32)> = self
+            .weight
+            .iter()
+            .map(|(id, weight)| Ok((resolve(id)?, *weight)))
+            .collect::<Result<_, String>>()?;
+        let drawable = stages.iter().any(|stage| {
+            weights
+                .iter()
+                .rfind(|(weighted, _)| weighted == stage)
+                .is_none_or(|&(_, weight)| weight > 0)
+        });
+        if !drawable {
+            return Err(
+                "every selected stage has a weight of 0, so none would ever run".to_string(),
+            );
+        }
+        Ok(weights)
     }
 }
 
+/// Look up a stage named on the command line, including scripted stages
+fn resolve(id: &str) -> Result<Stage, String> {
+    Stage::from_id(id).ok_or_else(|| {
+        format!(
+            "unknown stage '{}' (run with --help to list available stages)",
+            id
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn stage(id: &str) -> Stage {
+        Stage::from_id(id).unwrap()
+    }
+
+    /// Parse a command line given without the program name
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_from(["install-nothing"].iter().chain(args)).unwrap()
+    }
+
     #[test]
     fn test_default_to_all() {
-        let cli = Cli {
-            stages: vec![],
-            all: false,
-            exclude: vec![],
-        };
-        assert_eq!(cli.get_stages(), Stage::all());
+        let cli = parse(&[]);
+        assert_eq!(cli.get_stages().unwrap(), Stage::all());
     }
 
     #[test]
     fn test_explicit_all() {
-        let cli = Cli {
-            stages: vec![],
-            all: true,
-            exclude: vec![],
-        };
-        assert_eq!(cli.get_stages(), Stage::all());
+        let cli = parse(&["--all"]);
+        assert_eq!(cli.get_stages().unwrap(), Stage::all());
     }
 
     #[test]
     fn test_specific_stages() {
-        let cli = Cli {
-            stages: vec![Stage::Bios, Stage::Boot],
-            all: false,
-            exclude: vec![],
-        };
-        assert_eq!(cli.get_stages(), vec![Stage::Bios, Stage::Boot]);
+        let cli = parse(&["bios", "boot"]);
+        assert_eq!(
+            cli.get_stages().unwrap(),
+            vec![stage("bios"), stage("boot")]
+        );
     }
 
     #[test]
     fn test_exclude_single_stage() {
-        let cli = Cli {
-            stages: vec![],
-            all: false,
-            exclude: vec![Stage::Ai],
-        };
-        let result = cli.get_stages();
-        assert!(!result.contains(&Stage::Ai));
+        let cli = parse(&["--exclude", "ai"]);
+        let result = cli.get_stages().unwrap();
+        assert!(!result.contains(&stage("ai")));
         assert_eq!(result.len(), Stage::all().len() - 1);
     }
 
     #[test]
     fn test_exclude_multiple_stages() {
-        let cli = Cli {
-            stages: vec![],
-            all: true,
-            exclude: vec![Stage::Ai, Stage::Cloud],
-        };
-        let result = cli.get_stages();
-        assert!(!result.contains(&Stage::Ai));
-        assert!(!result.contains(&Stage::Cloud));
+        let cli = parse(&["--all", "--exclude", "ai", "cloud"]);
+        let result = cli.get_stages().unwrap();
+        assert!(!result.contains(&stage("ai")));
+        assert!(!result.contains(&stage("cloud")));
         assert_eq!(result.len(), Stage::all().len() - 2);
     }
 
     #[test]
     fn test_exclude_all_stages() {
-        let cli = Cli {
-            stages: vec![],
-            all: false,
-            exclude: Stage::all(),
-        };
-        let result = cli.get_stages();
+        let all: Vec<String> = Stage::all().iter().map(Stage::to_string).collect();
+        let mut args = vec!["--exclude"];
+        args.extend(all.iter().map(String::as_str));
+        let result = parse(&args).get_stages().unwrap();
         assert_eq!(result.len(), 0);
     }
+
+    #[test]
+    fn test_weights_need_weighted_order() {
+        let cli = parse(&["--weight", "kernel=3"]);
+        assert!(cli.get_weights(&Stage::all()).is_err());
+    }
+
+    #[test]
+    fn test_all_zero_weights_are_rejected() {
+        let args = [
+            "bios", "boot", "--order", "weighted", "--weight", "bios=0", "--weight", "boot=0",
+        ];
+        let cli = parse(&args);
+        let stages = cli.get_stages().unwrap();
+        assert!(cli.get_weights(&stages).is_err());
+
+        let cli = parse(&args[..args.len() - 2]);
+        assert_eq!(cli.get_weights(&stages).unwrap(), [(stages[0], 0)]);
+    }
 }
@@ -0,0 +1,138 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Slowest speed multiplier allowed, on the command line or from the keyboard
+pub const MIN_SPEED: f64 = 1.0 / 16.0;
+
+/// Playback speed multiplier stored as f64 bits; infinity means instant mode
+static SPEED: AtomicU64 = AtomicU64::new(0x3FF0_0000_0000_0000);
+
+/// Set the global speed multiplier (2.0 runs twice as fast, 0.5 half as fast)
+pub fn set_speed(speed: f64) {
+    SPEED.store(speed.to_bits(), Ordering::Relaxed);
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Speed for one test thread, so tests don't race each other over the global one
+    static THREAD_SPEED: std::cell::Cell<Option<f64>> = const { std::cell::Cell::new(None) };
+}
+
+/// Run everything on this thread at `speed`, whatever the global multiplier says
+#[cfg(test)]
+pub fn set_thread_speed(speed: f64) {
+    THREAD_SPEED.set(Some(speed));
+}
+
+/// Current global speed multiplier
+pub fn speed() -> f64 {
+    #[cfg(test)]
+    if let Some(speed) = THREAD_SPEED.get() {
+        return speed;
+    }
+    f64::from_bits(SPEED.load(Ordering::Relaxed))
+}
+
+/// Scale a simulated delay by the current speed multiplier
+pub fn scale(duration: Duration) -> Duration {
+    let speed = speed();
+    if speed.is_infinite() {
+        Duration::ZERO
+    } else {
+        Duration::try_from_secs_f64(duration.as_secs_f64() / speed).unwrap_or(Duration::MAX)
+    }
+}
+
+/// Sleep for a simulated delay, honoring the speed multiplier
+pub fn sleep(duration: Duration) {
+    let scaled = scale(duration);
+    if !scaled.is_zero() {
+        thread::sleep(scaled);
+    }
+}
+
+/// Parse a `--speed` value: a multiplier of at least 1/16 such as `0.25`, `10` or `10x`, or `instant`
+pub fn parse_speed(value: &str) -> Result<f64, String> {
+    if value.eq_ignore_ascii_case("instant") {
+        return Ok(f64::INFINITY);
+    }
+
+    let number = value.strip_suffix(['x', 'X']).unwrap_or(value);
+    match number.parse::<f64>() {
+        Ok(speed) if speed.is_finite() && speed >= MIN_SPEED => Ok(speed),
+        _ => Err(format!(
+            "invalid speed '{}', expected a multiplier of at least 0.0625 like 0.25, 10x or 'instant'",
+            value
+        )),
+    }
+}
+
+/// Parse a `--duration` value such as `90s`, `25m` or `1h30m`; bare numbers are seconds
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let invalid = || {
+        format!(
+            "invalid duration '{}', expected something like 90s, 25m or 1h30m",
+            value
+        )
+    };
+
+    let mut total = 0u64;
+    let mut number = String::new();
+    for c in value.trim().chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(invalid()),
+        };
+        let amount: u64 = number.parse().map_err(|_| invalid())?;
+        total = amount
+            .checked_mul(unit)
+            .and_then(|seconds| total.checked_add(seconds))
+            .ok_or_else(invalid)?;
+        number.clear();
+    }
+    if !number.is_empty() {
+        let seconds = number.parse::<u64>().map_err(|_| invalid())?;
+        total = total.checked_add(seconds).ok_or_else(invalid)?;
+    }
+
+    if total == 0 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs(total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_speed() {
+        assert_eq!(parse_speed("0.25"), Ok(0.25));
+        assert_eq!(parse_speed("10x"), Ok(10.0));
+        assert_eq!(parse_speed("instant"), Ok(f64::INFINITY));
+        assert!(parse_speed("0").is_err());
+        assert!(parse_speed("-2").is_err());
+        assert!(parse_speed("1e-20").is_err());
+        assert_eq!(parse_speed("0.0625"), Ok(MIN_SPEED));
+        assert!(parse_speed("fast").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("25m"), Ok(Duration::from_secs(1500)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5400)));
+        assert!(parse_duration("0s").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("5 minutes").is_err());
+        assert!(parse_duration("18446744073709551615h").is_err());
+        assert!(parse_duration("18446744073709551615m1").is_err());
+    }
+}
@@ -1,6 +1,12 @@
+use serde::{Deserialize, Deserializer};
+use std::fmt::Display;
+use std::fs;
+use std::io;
 use std::ops::Range;
+use std::path::{Path, PathBuf};
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SimulationConfig {
     pub ai: AiConfig,
     pub cloud: CloudConfig,
@@ -8,27 +14,159 @@ pub struct SimulationConfig {
     pub bios: BiosConfig,
     pub boot: BootConfig,
     pub bootloader: BootloaderConfig,
+    pub windows: WindowsConfig,
+    pub dos: DosConfig,
+    pub prompts: PromptsConfig,
+    pub boss: BossConfig,
 }
 
-impl Default for SimulationConfig {
-    fn default() -> Self {
-        Self {
-            ai: AiConfig::default(),
-            cloud: CloudConfig::default(),
-            container: ContainerConfig::default(),
-            bios: BiosConfig::default(),
-            boot: BootConfig::default(),
-            bootloader: BootloaderConfig::default(),
+impl SimulationConfig {
+    /// Load the configuration from an explicit path, the default location, or built-in defaults
+    pub fn load(path: Option<&Path>) -> io::Result<Self> {
+        match path {
+            Some(path) => Self::from_file(path),
+            None => match Self::default_path() {
+                Some(path) if path.is_file() => Self::from_file(&path),
+                _ => Ok(Self::default()),
+            },
+        }
+    }
+
+    /// Default config location: $XDG_CONFIG_HOME/install-nothing/config.toml
+    pub fn default_path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("config.toml"))
+    }
+
+    fn from_file(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        Self::from_toml(&content).map_err(|e| invalid_config(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Parse a TOML document, falling back to defaults for anything not specified
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(content).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let bios = &self.bios;
+        check_chance("bios.cmos_error_chance", bios.cmos_error_chance)?;
+        check_min_max("bios.erase", bios.erase_min, bios.erase_max)?;
+        check_min_max("bios.write", bios.write_min, bios.write_max)?;
+        check_min_max("bios.verify", bios.verify_min, bios.verify_max)?;
+
+        let boot = &self.boot;
+        check_range("boot.log_count_range", &boot.log_count_range)?;
+        check_range("boot.log_delay_range", &boot.log_delay_range)?;
+
+        let bootloader = &self.bootloader;
+        check_range(
+            "bootloader.kernel_scan_delay_range",
+            &bootloader.kernel_scan_delay_range,
+        )?;
+        check_chance(
+            "bootloader.windows_found_chance",
+            bootloader.windows_found_chance,
+        )?;
+        check_range(
+            "bootloader.write_stage_delay_range",
+            &bootloader.write_stage_delay_range,
+        )?;
+
+        let ai = &self.ai;
+        check_range(
+            "ai.model_download_speed_range",
+            &ai.model_download_speed_range,
+        )?;
+        check_chance("ai.failure_rate_network", ai.failure_rate_network)?;
+        check_chance("ai.failure_rate_checksum", ai.failure_rate_checksum)?;
+        check_chance("ai.failure_rate_kernel_panic", ai.failure_rate_kernel_panic)?;
+        check_chance("ai.failure_rate_oom", ai.failure_rate_oom)?;
+        check_range("ai.layer_load_delay_range", &ai.layer_load_delay_range)?;
+        check_range("ai.compilation_speed_range", &ai.compilation_speed_range)?;
+        check_range("ai.checksum_delay_range", &ai.checksum_delay_range)?;
+
+        let cloud = &self.cloud;
+        check_chance(
+            "cloud.failure_rate_rate_limit",
+            cloud.failure_rate_rate_limit,
+        )?;
+        check_chance(
+            "cloud.failure_rate_insufficient_capacity",
+            cloud.failure_rate_insufficient_capacity,
+        )?;
+        check_chance(
+            "cloud.failure_rate_dependency_violation",
+            cloud.failure_rate_dependency_violation,
+        )?;
+        check_chance(
+            "cloud.failure_rate_checksum_mismatch",
+            cloud.failure_rate_checksum_mismatch,
+        )?;
+        check_range("cloud.provision_speed_range", &cloud.provision_speed_range)?;
+
+        let container = &self.container;
+        check_chance(
+            "container.failure_rate_image_pull",
+            container.failure_rate_image_pull,
+        )?;
+        check_chance(
+            "container.failure_rate_readiness_probe",
+            container.failure_rate_readiness_probe,
+        )?;
+        check_chance(
+            "container.failure_rate_crash_loop",
+            container.failure_rate_crash_loop,
+        )?;
+        check_chance(
+            "container.probability_volume_mount",
+            container.probability_volume_mount,
+        )?;
+        check_chance(
+            "container.probability_secret_mount",
+            container.probability_secret_mount,
+        )?;
+        check_chance(
+            "container.probability_sidecar_injection",
+            container.probability_sidecar_injection,
+        )?;
+        check_range(
+            "container.layer_pull_speed_range",
+            &container.layer_pull_speed_range,
+        )?;
+
+        let windows = &self.windows;
+        check_range("windows.file_count_range", &windows.file_count_range)?;
+        if windows.file_count_range.start == 0 {
+            return Err(format!(
+                "windows.file_count_range must start at 1 or more, got [{}, {}]",
+                windows.file_count_range.start, windows.file_count_range.end
+            ));
         }
+        check_range("windows.file_delay_range", &windows.file_delay_range)?;
+        check_range(
+            "windows.billboard_delay_range",
+            &windows.billboard_delay_range,
+        )?;
+
+        let dos = &self.dos;
+        check_range("dos.disk_count_range", &dos.disk_count_range)?;
+        check_range("dos.copy_speed_range", &dos.copy_speed_range)?;
+        check_chance("dos.bad_disk_chance", dos.bad_disk_chance)?;
+
+        Ok(())
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct BiosConfig {
-    pub vendor: &'static str,
-    pub version: &'static str,
-    pub new_version: &'static str,
-    pub bios_date: &'static str,
+    pub vendor: String,
+    pub version: String,
+    pub new_version: String,
+    pub bios_date: String,
     pub header_delay: u64,
     pub post_start_delay: u64,
     pub cpu_detect_time: u64,
@@ -66,10 +204,10 @@ pub struct BiosConfig {
 impl Default for BiosConfig {
     fn default() -> Self {
         Self {
-            vendor: "American Megatrends BIOS (C)2003-2025",
-            version: "AMIBIOS v08.00.15",
-            new_version: "v08.00.16",
-            bios_date: "11/15/2025",
+            vendor: "American Megatrends BIOS (C)2003-2025".to_string(),
+            version: "AMIBIOS v08.00.15".to_string(),
+            new_version: "v08.00.16".to_string(),
+            bios_date: "11/15/2025".to_string(),
             header_delay: 400,
             post_start_delay: 400,
             cpu_detect_time: 800,
@@ -106,9 +244,12 @@ impl Default for BiosConfig {
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct BootConfig {
+    #[serde(deserialize_with = "range")]
     pub log_count_range: Range<usize>,
+    #[serde(deserialize_with = "range")]
     pub log_delay_range: Range<u64>,
     pub final_delay: u64,
 }
@@ -123,15 +264,18 @@ impl Default for BootConfig {
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct BootloaderConfig {
     pub install_delay: u64,
     pub probe_delay: u64,
     pub device_install_delay: u64,
     pub config_gen_delay: u64,
+    #[serde(deserialize_with = "range")]
     pub kernel_scan_delay_range: Range<u64>,
     pub windows_found_chance: f64,
     pub windows_delay: u64,
+    #[serde(deserialize_with = "range")]
     pub write_stage_delay_range: Range<u64>,
     pub finish_delay: u64,
 }
@@ -152,15 +296,20 @@ impl Default for BootloaderConfig {
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct AiConfig {
+    #[serde(deserialize_with = "range")]
     pub model_download_speed_range: Range<u64>,
     pub failure_rate_network: f64,
     pub failure_rate_checksum: f64,
     pub failure_rate_kernel_panic: f64,
     pub failure_rate_oom: f64,
+    #[serde(deserialize_with = "range")]
     pub layer_load_delay_range: Range<u64>,
+    #[serde(deserialize_with = "range")]
     pub compilation_speed_range: Range<u64>,
+    #[serde(deserialize_with = "range")]
     pub checksum_delay_range: Range<u64>,
 }
 
@@ -179,12 +328,14 @@ impl Default for AiConfig {
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct CloudConfig {
     pub failure_rate_rate_limit: f64,
     pub failure_rate_insufficient_capacity: f64,
     pub failure_rate_dependency_violation: f64,
     pub failure_rate_checksum_mismatch: f64,
+    #[serde(deserialize_with = "range")]
     pub provision_speed_range: Range<u64>,
 }
 
@@ -200,7 +351,8 @@ impl Default for CloudConfig {
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ContainerConfig {
     pub failure_rate_image_pull: f64,
     pub failure_rate_readiness_probe: f64,
@@ -208,6 +360,7 @@ pub struct ContainerConfig {
     pub probability_volume_mount: f64,
     pub probability_secret_mount: f64,
     pub probability_sidecar_injection: f64,
+    #[serde(deserialize_with = "range")]
     pub layer_pull_speed_range: Range<u64>,
 }
 
@@ -224,3 +377,228 @@ impl Default for ContainerConfig {
         }
     }
 }
+
+/// Which Windows setup the windows stage imitates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum WindowsEdition {
+    #[serde(rename = "95")]
+    Windows95,
+    #[serde(rename = "xp")]
+    Xp,
+    #[serde(rename = "10")]
+    Windows10,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WindowsConfig {
+    /// Edition to install; a different one is picked every run when unset
+    pub edition: Option<WindowsEdition>,
+    #[serde(deserialize_with = "range")]
+    pub file_count_range: Range<u32>,
+    #[serde(deserialize_with = "range")]
+    pub file_delay_range: Range<u64>,
+    pub countdown_tick: u64,
+    pub reboot_delay: u64,
+    #[serde(deserialize_with = "range")]
+    pub billboard_delay_range: Range<u64>,
+}
+
+impl Default for WindowsConfig {
+    fn default() -> Self {
+        Self {
+            edition: None,
+            file_count_range: 40..80,
+            file_delay_range: 60..180,
+            countdown_tick: 250,
+            reboot_delay: 800,
+            billboard_delay_range: 2000..4000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DosConfig {
+    #[serde(deserialize_with = "range")]
+    pub disk_count_range: Range<u32>,
+    #[serde(deserialize_with = "range")]
+    pub copy_speed_range: Range<u64>,
+    /// How long a disk prompt waits for ENTER before carrying on
+    pub prompt_timeout: u64,
+    pub bad_disk_chance: f64,
+}
+
+impl Default for DosConfig {
+    fn default() -> Self {
+        Self {
+            disk_count_range: 4..15,
+            copy_speed_range: 600..1600,
+            prompt_timeout: 3000,
+            bad_disk_chance: 0.1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PromptsConfig {
+    /// How long prompts wait for an answer before taking the default; 0 waits forever
+    pub timeout: u64,
+}
+
+impl Default for PromptsConfig {
+    fn default() -> Self {
+        Self { timeout: 15000 }
+    }
+}
+
+/// What the boss key covers the installer with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BossView {
+    /// A quiet `top` refreshing every couple of seconds
+    Top,
+    /// An idle shell prompt that echoes what is typed
+    Shell,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BossConfig {
+    pub view: BossView,
+    /// Prompt shown by the shell view
+    pub prompt: String,
+}
+
+impl Default for BossConfig {
+    fn default() -> Self {
+        Self {
+            view: BossView::Top,
+            prompt: "user@workstation:~$ ".to_string(),
+        }
+    }
+}
+
+/// Per-user directory for install-nothing files: $XDG_CONFIG_HOME/install-nothing
+pub fn config_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("install-nothing"))
+}
+
+/// Ranges are written as `[min, max]` in the config file, with `max` exclusive
+fn range<'de, D, T>(deserializer: D) -> Result<Range<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    let [start, end] = <[T; 2]>::deserialize(deserializer)?;
+    Ok(start..end)
+}
+
+fn check_chance(key: &str, value: f64) -> Result<(), String> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} must be between 0.0 and 1.0, got {}",
+            key, value
+        ))
+    }
+}
+
+fn check_range<T: PartialOrd + Display>(key: &str, range: &Range<T>) -> Result<(), String> {
+    if range.start < range.end {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} must be [min, max] with min < max, got [{}, {}]",
+            key, range.start, range.end
+        ))
+    }
+}
+
+fn check_min_max(key: &str, min: u64, max: u64) -> Result<(), String> {
+    if min < max {
+        Ok(())
+    } else {
+        Err(format!(
+            "{key}_min must be less than {key}_max, got {} and {}",
+            min, max
+        ))
+    }
+}
+
+fn invalid_config(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_override_keeps_defaults() {
+        let config = SimulationConfig::from_toml(
+            r#"
+            [bios]
+            vendor = "Phoenix Technologies"
+
+            [boot]
+            log_count_range = [2, 4]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.bios.vendor, "Phoenix Technologies");
+        assert_eq!(config.bios.version, BiosConfig::default().version);
+        assert_eq!(config.boot.log_count_range, 2..4);
+        assert_eq!(config.boot.final_delay, BootConfig::default().final_delay);
+    }
+
+    #[test]
+    fn test_unknown_key_rejected() {
+        let err = SimulationConfig::from_toml("[bios]\nvendr = \"typo\"\n").unwrap_err();
+        assert!(err.contains("vendr"));
+    }
+
+    #[test]
+    fn test_invalid_chance_rejected() {
+        let err = SimulationConfig::from_toml("[ai]\nfailure_rate_oom = 1.5\n").unwrap_err();
+        assert!(err.contains("ai.failure_rate_oom"));
+    }
+
+    #[test]
+    fn test_windows_edition() {
+        let config = SimulationConfig::from_toml("[windows]\nedition = \"xp\"\n").unwrap();
+        assert_eq!(config.windows.edition, Some(WindowsEdition::Xp));
+        assert!(SimulationConfig::from_toml("[windows]\nedition = \"me\"\n").is_err());
+    }
+
+    #[test]
+    fn test_boss_view() {
+        let config = SimulationConfig::from_toml("[boss]\nview = \"shell\"\n").unwrap();
+        assert_eq!(config.boss.view, BossView::Shell);
+        assert_eq!(config.boss.prompt, BossConfig::default().prompt);
+    }
+
+    #[test]
+    fn test_empty_range_rejected() {
+        let err = SimulationConfig::from_toml("[cloud]\nprovision_speed_range = [500, 500]\n")
+            .unwrap_err();
+        assert!(err.contains("cloud.provision_speed_range"));
+    }
+
+    #[test]
+    fn test_windows_copies_at_least_one_file() {
+        let err = SimulationConfig::from_toml(
+            "[windows]
+file_count_range = [0, 5]
+",
+        )
+        .unwrap_err();
+        assert!(err.contains("windows.file_count_range"));
+    }
+}
@@ -0,0 +1,161 @@
+use crate::clock;
+use crate::theme::Themed;
+use crate::ui;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::io;
+use std::time::Duration;
+
+/// Characters left behind by phosphor burn-in
+const BURN: [&str; 4] = ["░", "▒", "·", "▪"];
+
+/// Chances per finished line at full intensity
+const FLICKER_CHANCE: f64 = 0.05;
+const TEAR_CHANCE: f64 = 0.04;
+const BURN_CHANCE: f64 = 0.03;
+
+/// Glitches the terminal output the way a worn-out CRT would: lines flicker
+/// and tear, burn-in marks appear, and the picture rolls between stages
+pub struct Crt {
+    intensity: f64,
+    /// Text of the line currently on screen, since the last `\n` or `\r`
+    line: String,
+}
+
+impl Crt {
+    /// Effects at `intensity`, from 0 (none) to 1 (a monitor on its last legs)
+    pub fn new(intensity: f64) -> Self {
+        Self {
+            intensity,
+            line: String::new(),
+        }
+    }
+
+    /// Pass `text` to `send`, glitching some of the lines it finishes
+    pub fn apply(
+        &mut self,
+        text: &str,
+        send: &mut dyn FnMut(&str) -> io::Result<()>,
+    ) -> io::Result<()> {
+        for segment in text.split_inclusive('\n') {
+            let Some(body) = segment.strip_suffix('\n') else {
+                send(segment)?;
+                self.track(segment);
+                continue;
+            };
+
+            send(body)?;
+            self.track(body);
+            self.glitch(send)?;
+            send("\n")?;
+            self.line.clear();
+        }
+        Ok(())
+    }
+
+    fn track(&mut self, text: &str) {
+        match text.rfind('\r') {
+            Some(index) => self.line = text[index + 1..].to_string(),
+            None => self.line.push_str(text),
+        }
+    }
+
+    /// Maybe disturb the line the cursor is on, always leaving it readable afterwards
+    fn glitch(&self, send: &mut dyn FnMut(&str) -> io::Result<()>) -> io::Result<()> {
+        // A separate generator keeps seeded sessions identical with and without effects
+        let mut rng = rand::thread_rng();
+        let restore = format!("\r\x1b[2K{}", self.line);
+
+        if rng.gen_bool(FLICKER_CHANCE * self.intensity) {
+            send("\r\x1b[2K")?;
+            clock::sleep(Duration::from_millis(40));
+            send(&restore)?;
+        }
+
+        if rng.gen_bool(TEAR_CHANCE * self.intensity) {
+            let shift = rng.gen_range(2..=12);
+            send(&format!("\r\x1b[2K{}{}", " ".repeat(shift), self.line))?;
+            clock::sleep(Duration::from_millis(60));
+            send(&restore)?;
+        }
+
+        if rng.gen_bool(BURN_CHANCE * self.intensity) {
+            let (columns, _) = crossterm::terminal::size().unwrap_or((80, 24));
+            let column = ui::visible_width(&self.line) + rng.gen_range(4..24);
+            if column < columns as usize {
+                let mark = BURN.choose(&mut rng).unwrap();
+                send(&format!("\x1b[{}G{}", column + 1, mark.dim()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sometimes roll the picture as if vertical hold slipped; `in_place` scrolls the
+    /// screen and leaves the caller to redraw it, otherwise blank lines roll past
+    pub fn roll(
+        &mut self,
+        in_place: bool,
+        send: &mut dyn FnMut(&str) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let mut rng = rand::thread_rng();
+        if !rng.gen_bool(self.intensity) {
+            return Ok(());
+        }
+
+        let (_, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+        let step = ((rows as f64 * self.intensity / 6.0).ceil() as usize).max(1);
+        for _ in 0..3 {
+            if in_place {
+                send(&format!("\x1b[{}S", step))?;
+            } else {
+                send(&"\n".repeat(step))?;
+            }
+            clock::sleep(Duration::from_millis(30));
+        }
+        Ok(())
+    }
+}
+
+/// Parse a `--crt` value: effect intensity from 0 to 1
+pub fn parse_intensity(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(intensity) if (0.0..=1.0).contains(&intensity) => Ok(intensity),
+        _ => Err(format!(
+            "invalid CRT intensity '{}', expected a value between 0 and 1",
+            value
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(crt: &mut Crt, text: &str) -> String {
+        let mut out = String::new();
+        crt.apply(text, &mut |piece| {
+            out.push_str(piece);
+            Ok(())
+        })
+        .unwrap();
+        out
+    }
+
+    #[test]
+    fn test_zero_intensity_passes_text_through() {
+        let text = "spinner |\rspinner /\rdone\nnext\n";
+        assert_eq!(run(&mut Crt::new(0.0), text), text);
+    }
+
+    #[test]
+    fn test_glitches_restore_the_line() {
+        let mut crt = Crt::new(1.0);
+        for _ in 0..20 {
+            let out = run(&mut crt, "\rstatus ok\n");
+            let last = out.rsplit("\r\x1b[2K").next().unwrap();
+            let last = last.trim_start_matches('\r');
+            assert!(last.starts_with("status ok"), "{:?}", out);
+            assert!(out.ends_with('\n'));
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use crate::boss::BossScreen;
+use crate::clock;
+use crate::output;
+use crate::shutdown;
+use crate::ui;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Fastest speed reachable with `+`; `-` stops at `clock::MIN_SPEED`
+const MAX_SPEED: f64 = 64.0;
+
+/// How long the speed notice stays up after `+` or `-`
+const NOTICE_TIME: Duration = Duration::from_millis(1500);
+
+/// Why the running stage was asked to stop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interrupt {
+    /// Ctrl+C: abandon the installation
+    Cancel,
+    /// `q`: stop after tidying up, as if a run limit was reached
+    Quit,
+    /// `s`: drop the current stage and carry on with the next
+    Skip,
+    /// `f`: end the current stage with a simulated failure
+    Fail,
+}
+
+struct Input {
+    /// Interrupt waiting to be handled by the installer
+    pending: Option<Interrupt>,
+    /// Keys the controls don't use, kept for prompts
+    keys: VecDeque<KeyCode>,
+    /// When the current notice should be cleared
+    notice_until: Option<Instant>,
+}
+
+impl Input {
+    fn raise(&mut self, interrupt: Interrupt) {
+        // A cancel outranks anything already waiting
+        if self.pending.is_none() || interrupt == Interrupt::Cancel {
+            self.pending = Some(interrupt);
+        }
+    }
+}
+
+static INPUT: Mutex<Input> = Mutex::new(Input {
+    pending: None,
+    keys: VecDeque::new(),
+    notice_until: None,
+});
+
+/// The single keyboard handler: applies pause and speed keys, records interrupts and
+/// queues other keys. Returns `true` while an interrupt is waiting, so every
+/// `exit_check` and animation that calls it unwinds
+pub fn check() -> bool {
+    while event::poll(Duration::ZERO).unwrap_or(false) {
+        let Ok(Event::Key(key)) = event::read() else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Release {
+            handle(key);
+        }
+    }
+
+    let mut input = INPUT.lock().unwrap();
+    if input
+        .notice_until
+        .is_some_and(|until| Instant::now() >= until)
+    {
+        input.notice_until = None;
+        drop(input);
+        let _ = output::notice(None);
+        input = INPUT.lock().unwrap();
+    }
+    input.pending.is_some()
+}
+
+fn handle(key: KeyEvent) {
+    let interrupt = match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Interrupt::Cancel)
+        }
+        KeyCode::Char('q') => Some(Interrupt::Quit),
+        KeyCode::Char('s') => Some(Interrupt::Skip),
+        KeyCode::Char('f') => Some(Interrupt::Fail),
+        KeyCode::Char(' ') => {
+            pause();
+            None
+        }
+        KeyCode::Char('b') => {
+            hide();
+            None
+        }
+        KeyCode::Char('+') | KeyCode::Char('=') => {
+            change_speed(2.0);
+            None
+        }
+        KeyCode::Char('-') => {
+            change_speed(0.5);
+            None
+        }
+        code => {
+            INPUT.lock().unwrap().keys.push_back(code);
+            None
+        }
+    };
+
+    if let Some(interrupt) = interrupt {
+        raise(interrupt);
+    }
+}
+
+/// Ask the running stage to stop, as if the key for `interrupt` was pressed
+pub fn raise(interrupt: Interrupt) {
+    INPUT.lock().unwrap().raise(interrupt);
+}
+
+/// Hold everything until space is pressed again; Ctrl+C and `q` still work
+fn pause() {
+    let _ = output::notice(Some(" PAUSED - press space to resume "));
+    while shutdown::signal().is_none() {
+        if let Ok(true) = event::poll(Duration::from_millis(100)) {
+            if let Ok(Event::Key(key)) = event::read() {
+                if key.kind == KeyEventKind::Release {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char(' ') => break,
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        handle(key);
+                        break;
+                    }
+                    KeyCode::Char('q') => {
+                        handle(key);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    INPUT.lock().unwrap().notice_until = None;
+    let _ = output::notice(None);
+}
+
+/// Boss key: cover the installer with a calm screen until `b` is pressed again
+/// (Esc or F12 from the shell view, where `b` is typed). Ctrl+C still works, and
+/// comes back to the installer to cancel
+fn hide() {
+    if ui::is_plain() {
+        return;
+    }
+
+    let _ = output::cover(|out| {
+        let mut screen = BossScreen::new();
+        screen.draw(out)?;
+        let mut drawn = Instant::now();
+        while shutdown::signal().is_none() {
+            if screen
+                .refresh()
+                .is_some_and(|refresh| drawn.elapsed() >= refresh)
+            {
+                screen.draw(out)?;
+                drawn = Instant::now();
+            }
+            if !event::poll(Duration::from_millis(100))? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+            match key.code {
+                code if screen.leaves_on(code) => return Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    handle(key);
+                    return Ok(());
+                }
+                code => screen.key(code, out)?,
+            }
+        }
+        Ok(())
+    });
+}
+
+/// `speed` changed by `factor` within the range the keys allow; instant stays instant
+fn scaled_speed(speed: f64, factor: f64) -> Option<f64> {
+    (!speed.is_infinite()).then(|| (speed * factor).clamp(clock::MIN_SPEED, MAX_SPEED))
+}
+
+fn change_speed(factor: f64) {
+    let Some(speed) = scaled_speed(clock::speed(), factor) else {
+        return;
+    };
+    clock::set_speed(speed);
+    let _ = output::notice(Some(&format!(" Speed {}x ", speed)));
+    INPUT.lock().unwrap().notice_until = Some(Instant::now() + NOTICE_TIME);
+}
+
+/// Take the waiting interrupt, if any, so the run can carry on
+pub fn take_interrupt() -> Option<Interrupt> {
+    INPUT.lock().unwrap().pending.take()
+}
+
+/// Next key pressed that isn't one of the controls
+pub fn take_key() -> Option<KeyCode> {
+    check();
+    INPUT.lock().unwrap().keys.pop_front()
+}
+
+/// Forget keys pressed so far, so a prompt only takes an answer typed while it shows
+pub fn discard_keys() {
+    check();
+    INPUT.lock().unwrap().keys.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input() -> Input {
+        Input {
+            pending: None,
+            keys: VecDeque::new(),
+            notice_until: None,
+        }
+    }
+
+    #[test]
+    fn test_cancel_outranks_waiting_interrupts() {
+        let mut input = input();
+        input.raise(Interrupt::Skip);
+        input.raise(Interrupt::Quit);
+        assert_eq!(input.pending, Some(Interrupt::Skip));
+        input.raise(Interrupt::Cancel);
+        input.raise(Interrupt::Fail);
+        assert_eq!(input.pending, Some(Interrupt::Cancel));
+    }
+
+    #[test]
+    fn test_speed_keys_stay_in_range() {
+        assert_eq!(scaled_speed(1.0, 2.0), Some(2.0));
+        assert_eq!(scaled_speed(MAX_SPEED, 2.0), Some(MAX_SPEED));
+        assert_eq!(scaled_speed(clock::MIN_SPEED, 0.5), Some(clock::MIN_SPEED));
+        assert_eq!(scaled_speed(f64::INFINITY, 0.5), None);
+    }
+}
@@ -1,71 +1,118 @@
-use crate::cli::Stage;
+use crate::clock;
+use crate::config::SimulationConfig;
+use crate::input::{self, Interrupt};
 use crate::messages::{EASTER_EGGS, RETRY_MESSAGES, WARNINGS};
-use crate::stages::selected_stages;
-use crate::ui::Spinner;
+use crate::ordering::StageOrdering;
+use crate::output;
+use crate::rng::{self, SessionRng};
+use crate::stages::{selected_stages, EulaStage, InstallationStage, Stage};
+use crate::theme::Themed;
+use crate::ui::{self, Spinner};
 use colored::*;
 use crossterm::{
-    cursor,
-    event::{self, Event, KeyCode},
-    execute,
+    cursor, execute,
     terminal::{self, ClearType},
 };
 use rand::Rng;
 use std::io;
-use std::thread;
-use std::time::Duration;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// When `--duration` runs out, for the exit check to stop whatever is running
+static DEADLINE: Mutex<Option<Instant>> = Mutex::new(None);
+
+fn past_deadline() -> bool {
+    DEADLINE
+        .lock()
+        .unwrap()
+        .is_some_and(|deadline| Instant::now() >= deadline)
+}
+
+/// Optional limits that end an otherwise endless installation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunLimits {
+    pub cycles: Option<u32>,
+    pub duration: Option<Duration>,
+}
+
+impl RunLimits {
+    fn out_of_time(&self, started: Instant) -> bool {
+        self.duration
+            .is_some_and(|duration| started.elapsed() >= duration)
+    }
+
+    fn reached(&self, cycle: u32, started: Instant) -> bool {
+        self.cycles.is_some_and(|cycles| cycle >= cycles) || self.out_of_time(started)
+    }
+}
 
 pub struct Installer {
-    rng: rand::rngs::ThreadRng,
+    rng: SessionRng,
     selected_stages: Vec<Stage>,
+    config: SimulationConfig,
+    ordering: StageOrdering,
+    limits: RunLimits,
+    eula: bool,
 }
 
 impl Installer {
-    pub fn new(stages: Vec<Stage>) -> Self {
+    pub fn new(
+        stages: Vec<Stage>,
+        config: SimulationConfig,
+        ordering: StageOrdering,
+        limits: RunLimits,
+    ) -> Self {
         Self {
-            rng: rand::thread_rng(),
+            rng: rng::session(),
             selected_stages: stages,
+            config,
+            ordering,
+            limits,
+            eula: false,
         }
     }
 
-    fn check_exit(&self) -> bool {
-        if event::poll(Duration::from_millis(0)).unwrap_or(false) {
-            if let Ok(Event::Key(key_event)) = event::read() {
-                if key_event.code == KeyCode::Char('c')
-                    && key_event.modifiers.contains(event::KeyModifiers::CONTROL)
-                {
-                    return true;
-                }
-            }
+    /// Have the license agreement accepted before the first cycle
+    pub fn with_eula(mut self, eula: bool) -> Self {
+        self.eula = eula;
+        self
+    }
+
+    /// Exit check handed to every stage and animation; see `input::check`. Running out
+    /// of `--duration` stops the current stage the same way `q` does
+    pub fn check_exit() -> bool {
+        if past_deadline() {
+            input::raise(Interrupt::Quit);
         }
-        false
+        input::check()
     }
 
     fn print_header(&self) {
-        println!(
+        outln!(
             "{}",
-            "=================================================================".bright_cyan()
+            "=================================================================".header()
         );
-        println!(
+        outln!(
             "{}",
             "         UNIVERSAL SYSTEM INSTALLER v3.2.1 (Build 1999)"
-                .bright_white()
+                .emphasis()
                 .bold()
         );
-        println!(
+        outln!(
             "{}",
-            "=================================================================".bright_cyan()
+            "=================================================================".header()
         );
-        println!();
-        thread::sleep(Duration::from_millis(1500));
+        outln!();
+        clock::sleep(Duration::from_millis(1500));
     }
 
     fn show_easter_egg(&mut self) -> io::Result<()> {
         if self.rng.gen_bool(0.15) {
-            println!();
+            outln!();
             let egg = EASTER_EGGS[self.rng.gen_range(0..EASTER_EGGS.len())];
             let mut spinner = Spinner::new();
-            spinner.animate(egg, 1500, &|| self.check_exit())?;
-            println!();
+            spinner.animate(egg, 1500, &|| Self::check_exit())?;
+            outln!();
         }
         Ok(())
     }
@@ -73,104 +120,252 @@ impl Installer {
     fn show_warning(&mut self) {
         if self.rng.gen_bool(0.2) {
             let warning = WARNINGS[self.rng.gen_range(0..WARNINGS.len())];
-            println!("\n{}", warning.yellow());
-            thread::sleep(Duration::from_millis(1000));
-            println!("{}", "Continuing anyway...".dimmed());
-            println!();
+            outln!("\n{}", warning.warning());
+            clock::sleep(Duration::from_millis(1000));
+            outln!("{}", "Continuing anyway...".dim());
+            outln!();
         }
     }
 
     fn show_retry(&mut self) -> io::Result<()> {
         if self.rng.gen_bool(0.1) {
             let message = RETRY_MESSAGES[self.rng.gen_range(0..RETRY_MESSAGES.len())];
-            println!("\n{}", message.yellow());
-            thread::sleep(Duration::from_millis(800));
+            outln!("\n{}", message.warning());
+            clock::sleep(Duration::from_millis(800));
 
             let mut spinner = Spinner::new();
             spinner.animate("Reconnecting to mirror.oldsoft.org", 1200, &|| {
-                self.check_exit()
+                Self::check_exit()
             })?;
-            println!();
+            outln!();
         }
         Ok(())
     }
 
-    pub fn run(&mut self) -> io::Result<()> {
-        terminal::enable_raw_mode()?;
-        execute!(
-            io::stdout(),
-            terminal::Clear(ClearType::All),
-            cursor::MoveTo(0, 0)
-        )?;
-        terminal::disable_raw_mode()?;
+    fn run_stage(&mut self, index: usize, stage: &dyn InstallationStage) -> io::Result<()> {
+        if Self::check_exit() {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
+        }
+
+        self.show_easter_egg()?;
+        self.show_warning();
+        self.show_retry()?;
+
+        output::roll()?;
+        output::update_run_status(|status| status.start_stage(index))?;
+        stage.run(&|| Self::check_exit())
+    }
+
+    /// Act on a stage cut short by a key press: returns whether to keep going,
+    /// or the error itself for Ctrl+C and real failures
+    fn handle_interrupt(&mut self, error: io::Error) -> io::Result<bool> {
+        if error.kind() != io::ErrorKind::Interrupted {
+            return Err(error);
+        }
 
-        self.print_header();
+        match input::take_interrupt() {
+            Some(Interrupt::Skip) => {
+                outln!();
+                outln!("{}", "Skipping stage at user request...".warning());
+                Ok(true)
+            }
+            Some(Interrupt::Fail) => {
+                let code = self.rng.gen_range(1..=255);
+                outln!();
+                outln!(
+                    "{} {}",
+                    "FATAL:".error().bold(),
+                    format!("stage terminated unexpectedly (exit code {})", code).error()
+                );
+                clock::sleep(Duration::from_millis(800));
+                outln!("{}", "Rolling back changes...".dim());
+                clock::sleep(Duration::from_millis(1200));
+                outln!("{}", "Continuing with next stage anyway.".warning());
+                Ok(true)
+            }
+            Some(Interrupt::Quit) => {
+                let reason = if past_deadline() {
+                    "Time limit reached, stopping..."
+                } else {
+                    "Stopping at user request..."
+                };
+                outln!();
+                outln!("{}", reason.warning());
+                Ok(false)
+            }
+            Some(Interrupt::Cancel) | None => Err(error),
+        }
+    }
 
-        println!(
+    fn print_summary(&self, cycles: u32, elapsed: Duration) {
+        let secs = elapsed.as_secs();
+        outln!(
+            "\n{}",
+            "═══════════════════════════════════════════════════════════════".header()
+        );
+        outln!("{}", "Installation complete.".success().bold());
+        outln!(
             "{}",
-            "Initializing installation environment...".bright_white()
+            format!(
+                "{} installation cycle{} finished in {:02}:{:02}:{:02}.",
+                cycles,
+                if cycles == 1 { "" } else { "s" },
+                secs / 3600,
+                secs / 60 % 60,
+                secs % 60
+            )
+            .emphasis()
         );
-        thread::sleep(Duration::from_millis(1000));
+        outln!(
+            "{}",
+            "Thank you for using Universal System Installer!".emphasis()
+        );
+        outln!(
+            "{}",
+            "═══════════════════════════════════════════════════════════════".header()
+        );
+    }
+
+    pub fn run(&mut self) -> io::Result<()> {
+        let started = Instant::now();
+        *DEADLINE.lock().unwrap() = self
+            .limits
+            .duration
+            .and_then(|duration| started.checked_add(duration));
+
+        // Raw mode lets the keyboard controls see single key presses
+        let raw = !ui::is_plain();
+        if raw {
+            terminal::enable_raw_mode()?;
+        }
+        if ui::is_dashboard() {
+            output::start_dashboard()?;
+        } else if !ui::is_plain() {
+            execute!(
+                output::stdout(),
+                terminal::Clear(ClearType::All),
+                cursor::MoveTo(0, 0)
+            )?;
+            if ui::is_status_line() {
+                output::start_status_line()?;
+            }
+        }
+
+        let result = self.run_cycles(started);
+        // Every step runs even when an earlier one fails (the terminal may be gone
+        // after SIGHUP), so raw mode is always switched off
+        let teardown = [
+            output::stop_dashboard(),
+            output::stop_status_line(),
+            if raw {
+                terminal::disable_raw_mode()
+            } else {
+                Ok(())
+            },
+        ];
+        let cycles = result?;
+        teardown.into_iter().collect::<io::Result<()>>()?;
+
+        self.print_summary(cycles, started.elapsed());
+        Ok(())
+    }
+
+    /// Run installation cycles until a limit is reached, returning how many were completed
+    fn run_cycles(&mut self, started: Instant) -> io::Result<u32> {
+        if !ui::is_dashboard() {
+            self.print_header();
+        }
+
+        outln!("{}", "Initializing installation environment...".emphasis());
+        clock::sleep(Duration::from_millis(1000));
 
         let mut spinner = Spinner::new();
-        spinner.animate("Detecting hardware configuration...", 1500, &|| {
-            self.check_exit()
-        })?;
-        println!();
+        let detected = spinner.animate("Detecting hardware configuration...", 1500, &|| {
+            Self::check_exit()
+        });
+        if let Err(e) = detected {
+            if !self.handle_interrupt(e)? {
+                return Ok(0);
+            }
+        }
+        outln!();
+
+        if self.eula {
+            if let Err(e) = EulaStage.run(&|| Self::check_exit()) {
+                if !self.handle_interrupt(e)? {
+                    return Ok(0);
+                }
+            }
+        }
 
         let mut cycle = 0;
-        loop {
+        let mut completed = 0;
+        'cycles: loop {
             cycle += 1;
 
             if cycle > 1 {
-                println!(
+                outln!(
                     "\n{}",
-                    "═══════════════════════════════════════════════════════════════"
-                        .bright_magenta()
+                    "═══════════════════════════════════════════════════════════════".header()
                 );
-                println!(
+                outln!(
                     "{}",
                     format!("Beginning installation cycle #{}...", cycle)
-                        .bright_magenta()
+                        .header()
                         .bold()
                 );
-                println!(
+                outln!(
                     "{}",
-                    "═══════════════════════════════════════════════════════════════"
-                        .bright_magenta()
+                    "═══════════════════════════════════════════════════════════════".header()
                 );
-                thread::sleep(Duration::from_millis(1000));
+                clock::sleep(Duration::from_millis(1000));
             }
 
-            let stages = selected_stages(&self.selected_stages);
+            let order = self.ordering.arrange(&self.selected_stages, &mut self.rng);
+            let stages = selected_stages(&order, &self.config);
+            let infos = stages.iter().map(|stage| stage.info()).collect();
+            output::update_run_status(|status| status.start_cycle(cycle, infos))?;
 
-            for stage in stages {
-                if self.check_exit() {
-                    return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
+            for (index, stage) in stages.iter().enumerate() {
+                if self.limits.out_of_time(started) {
+                    break 'cycles;
                 }
 
-                self.show_easter_egg()?;
-                self.show_warning();
-                self.show_retry()?;
+                let result = self.run_stage(index, stage.as_ref());
+                if let Err(e) = result {
+                    if !self.handle_interrupt(e)? {
+                        break 'cycles;
+                    }
+                }
 
-                stage.run(&|| self.check_exit())?;
+                clock::sleep(Duration::from_millis(self.rng.gen_range(300..800)));
+            }
+            completed = cycle;
 
-                thread::sleep(Duration::from_millis(self.rng.gen_range(300..800)));
+            if self.limits.reached(cycle, started) {
+                break;
             }
 
-            println!(
+            outln!(
                 "\n{}",
                 "Installation complete! Restarting installation process..."
-                    .bright_green()
+                    .success()
                     .bold()
             );
-            thread::sleep(Duration::from_millis(2000));
+            clock::sleep(Duration::from_millis(2000));
         }
+
+        Ok(completed)
     }
 }
 
 impl Default for Installer {
     fn default() -> Self {
-        Self::new(Stage::all())
+        Self::new(
+            Stage::all(),
+            SimulationConfig::default(),
+            StageOrdering::default(),
+            RunLimits::default(),
+        )
     }
 }
@@ -12,7 +12,7 @@ impl KernelLogs {
         let logs = content
             .lines()
             .filter(|line| !line.trim().is_empty())
-            .map(|line| Self::strip_timestamp(line))
+            .map(Self::strip_timestamp)
             .collect();
 
         Self { logs }
@@ -37,7 +37,7 @@ impl KernelLogs {
 
     /// Get multiple random kernel log messages
     pub fn random_batch(&self, count: usize) -> Vec<&str> {
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
         let mut batch = Vec::new();
 
         for _ in 0..count {
@@ -8,20 +8,19 @@ static LAST_TIMESTAMP: LazyLock<Mutex<f64>> = LazyLock::new(|| Mutex::new(0.0));
 
 impl LogGenerator {
     pub fn timestamp() -> String {
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
         let mut last = LAST_TIMESTAMP.lock().unwrap();
         *last += rng.gen_range(0.01..0.5);
         format!("[{:12.6}]", *last)
     }
 
     pub fn hex_addr() -> String {
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
         format!("0x{:016x}", rng.gen::<u64>())
     }
 
-    #[allow(dead_code)]
     pub fn version() -> String {
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
         format!(
             "v{}.{}.{}",
             rng.gen_range(1..10),
@@ -1,20 +1,39 @@
+#[macro_use]
+mod output;
+
+mod asciicast;
+mod boss;
 mod build_logs;
 mod cli;
+mod clock;
 mod config;
+mod crt;
 mod deno_logs;
+mod input;
 mod installer;
 mod kernel_logs;
 mod log_generator;
 mod messages;
+mod ordering;
+mod rng;
+mod serial;
+mod shutdown;
 mod stages;
+mod theme;
 mod ui;
 
-use clap::Parser;
-use cli::Cli;
-use colored::*;
-use installer::Installer;
-use rand::seq::SliceRandom;
-use std::io;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use cli::{Cli, Command};
+use config::SimulationConfig;
+use crossterm::terminal;
+use crt::Crt;
+use installer::{Installer, RunLimits};
+use ordering::StageOrdering;
+use serial::SerialLine;
+use std::io::{self, IsTerminal};
+use std::time::Duration;
+use theme::Themed;
 
 fn main() {
     if let Err(e) = run_installer() {
@@ -23,33 +42,105 @@ fn main() {
 }
 
 fn run_installer() -> io::Result<()> {
+    // Scripted stages are registered before parsing so --help can list them. A broken
+    // script mustn't break --help or --version, so its error waits until a run starts
+    let script_errors: Vec<io::Error> = config::config_dir()
+        .map(|dir| stages::load_script_dir(&dir.join("stages")))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(Result::err)
+        .collect();
+
     let cli = Cli::parse();
-    let mut stages = cli.get_stages();
+    shutdown::install()?;
+    let config = SimulationConfig::load(cli.config.as_deref())?;
+    for path in &cli.stage_file {
+        stages::load_script(path)?;
+    }
+    let selection = cli.get_stages().and_then(|stages| {
+        let weights = cli.get_weights(&stages)?;
+        Ok((stages, weights))
+    });
+    let (stages, weights) = match selection {
+        Ok(selection) => selection,
+        Err(e) => Cli::command().error(ErrorKind::InvalidValue, e).exit(),
+    };
+
+    clock::set_speed(cli.speed);
+    theme::set_theme(cli.theme);
+    ui::set_spinner_style(cli.spinner);
+    ui::set_plain(cli.plain || !io::stdout().is_terminal());
+    ui::set_dashboard(cli.dashboard && !ui::is_plain());
+    ui::set_status_line(cli.status_line && !ui::is_plain());
+    ui::set_unattended(cli.unattended);
+    boss::configure(config.boss.clone());
+    ui::set_prompt_timeout(
+        Some(Duration::from_millis(config.prompts.timeout)).filter(|timeout| !timeout.is_zero()),
+    );
+    if let Some(baud) = cli.baud {
+        // Garbage characters would end up in plain logs, so noise is terminal-only
+        let noise = if ui::is_plain() {
+            0.0
+        } else {
+            cli.line_noise.unwrap_or(0.0)
+        };
+        output::set_serial_line(SerialLine::new(baud, noise));
+    }
+    if let Some(intensity) = cli.crt {
+        // Effects only make sense on a live screen; plain output stays untouched
+        if !ui::is_plain() {
+            output::set_crt(Crt::new(intensity));
+        }
+    }
+
+    if let Some(path) = &cli.record {
+        output::start_recording(path)?;
+    }
+
+    if let Some(Command::Replay { path }) = &cli.command {
+        // Raw mode lets the playback controls see single key presses
+        let raw = !ui::is_plain();
+        if raw {
+            terminal::enable_raw_mode()?;
+        }
+        let result = asciicast::play(path, &Installer::check_exit);
+        let restored = if raw {
+            terminal::disable_raw_mode()
+        } else {
+            Ok(())
+        };
+        return result.and(restored);
+    }
 
-    let mut rng = rand::thread_rng();
-    stages.shuffle(&mut rng);
+    if !script_errors.is_empty() {
+        let messages: Vec<String> = script_errors.iter().map(ToString::to_string).collect();
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            messages.join("\n"),
+        ));
+    }
 
-    let mut installer = Installer::new(stages);
+    if let Some(seed) = cli.seed {
+        rng::seed(seed);
+    }
+
+    let ordering = StageOrdering::new(cli.order, &weights);
+    let limits = RunLimits {
+        cycles: cli.cycles,
+        duration: cli.duration,
+    };
+    let mut installer = Installer::new(stages, config, ordering, limits).with_eula(cli.eula);
     installer.run()
 }
 
 fn handle_error(e: io::Error) {
+    // A signal may also surface as a failed write once the terminal has gone away
+    if let Some(signal) = shutdown::write_failure(&e) {
+        shutdown::exit(signal);
+    }
     if e.kind() == io::ErrorKind::Interrupted {
-        println!(
-            "\n\n{}",
-            "═══════════════════════════════════════".bright_cyan()
-        );
-        println!("{}", "Installation cancelled by user.".bright_white());
-        println!(
-            "{}",
-            "Thank you for using Universal System Installer!".bright_white()
-        );
-        println!(
-            "{}",
-            "═══════════════════════════════════════".bright_cyan()
-        );
-    } else {
-        eprintln!("\n{} {:?}", "Error:".bright_red(), e);
-        std::process::exit(1);
+        shutdown::exit(shutdown::SIGINT);
     }
+    eprintln!("\n{} {}", "Error:".error(), e);
+    std::process::exit(1);
 }
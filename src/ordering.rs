@@ -0,0 +1,120 @@
+use crate::stages::Stage;
+use clap::ValueEnum;
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use std::collections::HashMap;
+
+/// How the selected stages are arranged for each installation cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum StageOrder {
+    /// Run stages in the order they were given on the command line
+    Given,
+    /// Run stages in canonical installation order (BIOS first)
+    Canonical,
+    /// Shuffle the stages every cycle
+    #[default]
+    Shuffle,
+    /// Draw stages at random every cycle, favoring stages with a higher --weight
+    Weighted,
+}
+
+/// Arranges the selected stages for each cycle according to a `StageOrder`
+#[derive(Debug, Clone, Default)]
+pub struct StageOrdering {
+    order: StageOrder,
+    weights: HashMap<Stage, u32>,
+}
+
+impl StageOrdering {
+    pub fn new(order: StageOrder, weights: &[(Stage, u32)]) -> Self {
+        Self {
+            order,
+            weights: weights.iter().copied().collect(),
+        }
+    }
+
+    /// Weight of a stage in weighted mode; stages without an explicit weight count as 1
+    fn weight(&self, stage: Stage) -> u32 {
+        self.weights.get(&stage).copied().unwrap_or(1)
+    }
+
+    /// Produce the stage sequence for one installation cycle
+    pub fn arrange<R: Rng>(&self, stages: &[Stage], rng: &mut R) -> Vec<Stage> {
+        match self.order {
+            StageOrder::Given => stages.to_vec(),
+            StageOrder::Canonical => Stage::registered()
+                .into_iter()
+                .filter(|stage| stages.contains(stage))
+                .collect(),
+            StageOrder::Shuffle => {
+                let mut arranged = stages.to_vec();
+                arranged.shuffle(rng);
+                arranged
+            }
+            StageOrder::Weighted => {
+                let weights: Vec<u32> = stages.iter().map(|&stage| self.weight(stage)).collect();
+                match WeightedIndex::new(&weights) {
+                    Ok(distribution) => (0..stages.len())
+                        .map(|_| stages[distribution.sample(rng)])
+                        .collect(),
+                    Err(_) => Vec::new(),
+                }
+            }
+        }
+    }
+}
+
+/// Parse a `--weight` value such as `kernel=3`; the stage name is resolved later
+pub fn parse_weight(value: &str) -> Result<(String, u32), String> {
+    let (name, weight) = value
+        .split_once('=')
+        .ok_or_else(|| format!("invalid weight '{}', expected STAGE=N", value))?;
+    let weight = weight
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid weight '{}', expected a whole number", weight))?;
+    Ok((name.trim().to_string(), weight))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+
+    fn stage(id: &str) -> Stage {
+        Stage::from_id(id).unwrap()
+    }
+
+    #[test]
+    fn test_given_keeps_order() {
+        let ordering = StageOrdering::new(StageOrder::Given, &[]);
+        let stages = vec![stage("kernel"), stage("bios"), stage("boot")];
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(ordering.arrange(&stages, &mut rng), stages);
+    }
+
+    #[test]
+    fn test_canonical_follows_registry_order() {
+        let ordering = StageOrdering::new(StageOrder::Canonical, &[]);
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(
+            ordering.arrange(&[stage("kernel"), stage("boot"), stage("bios")], &mut rng),
+            vec![stage("bios"), stage("boot"), stage("kernel")]
+        );
+    }
+
+    #[test]
+    fn test_weighted_skips_zero_weight() {
+        let ordering = StageOrdering::new(StageOrder::Weighted, &[(stage("boot"), 0)]);
+        let mut rng = StdRng::seed_from_u64(1);
+        let arranged = ordering.arrange(&[stage("bios"), stage("boot")], &mut rng);
+        assert_eq!(arranged, vec![stage("bios"), stage("bios")]);
+    }
+
+    #[test]
+    fn test_parse_weight() {
+        assert_eq!(parse_weight("kernel=3"), Ok(("kernel".to_string(), 3)));
+        assert!(parse_weight("kernel").is_err());
+        assert!(parse_weight("kernel=lots").is_err());
+    }
+}
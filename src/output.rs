@@ -0,0 +1,370 @@
+use crate::asciicast::Recorder;
+use crate::crt::Crt;
+use crate::serial::SerialLine;
+use crate::theme::Themed;
+use crate::ui::{self, Dashboard, RunStatus, StatusLine};
+use colored::Colorize;
+use crossterm::{cursor, queue, style::Print, terminal};
+use std::borrow::Cow;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+
+/// Print to the installer output, like `print!`
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::output::print(format_args!($($arg)*))
+    };
+}
+
+/// Print a line to the installer output, like `println!`
+macro_rules! outln {
+    () => {
+        $crate::output::print(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::print(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+/// Single sink for everything the installer writes to the terminal
+struct Output {
+    recorder: Option<Recorder>,
+    /// When set, output feeds the dashboard's log pane instead of scrolling past
+    dashboard: Option<Dashboard>,
+    /// When set, a status line is kept on the bottom row below the scrolling output
+    status_line: Option<StatusLine>,
+    /// When set, output is paced through a simulated serial line
+    serial: Option<SerialLine>,
+    /// When set, terminal output is run through CRT glitch effects
+    crt: Option<Crt>,
+}
+
+static OUTPUT: LazyLock<Mutex<Output>> = LazyLock::new(|| {
+    Mutex::new(Output {
+        recorder: None,
+        dashboard: None,
+        status_line: None,
+        serial: None,
+        crt: None,
+    })
+});
+
+impl Output {
+    fn write(&mut self, text: &str) -> io::Result<()> {
+        match &mut self.dashboard {
+            Some(dashboard) => {
+                dashboard.feed(text);
+                let frame = dashboard.render();
+                self.emit(&frame)
+            }
+            None => {
+                self.emit(text)?;
+                self.refresh_status_line(false)
+            }
+        }
+    }
+
+    /// Redraw the status line if it is due, or right away with `force`
+    fn refresh_status_line(&mut self, force: bool) -> io::Result<()> {
+        let Some(status_line) = &mut self.status_line else {
+            return Ok(());
+        };
+        let frame = status_line.render(force);
+        if frame.is_empty() {
+            return Ok(());
+        }
+        self.overlay(&frame)
+    }
+
+    /// Send text that draws over the output to the terminal and the recording,
+    /// skipping the CRT effects meant for the output itself
+    fn overlay(&mut self, text: &str) -> io::Result<()> {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(text)?;
+        }
+        transmit(&mut self.serial, text)
+    }
+
+    /// Send text to the terminal and the recording; the recording gets it
+    /// before CRT effects, serial pacing and noise, so it stays clean
+    fn emit(&mut self, text: &str) -> io::Result<()> {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(text)?;
+        }
+        let serial = &mut self.serial;
+        match &mut self.crt {
+            // Dashboard frames position every row themselves, so only whole-screen rolls apply
+            Some(crt) if self.dashboard.is_none() => {
+                crt.apply(text, &mut |piece| transmit(serial, piece))
+            }
+            _ => transmit(serial, text),
+        }
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Set on test threads whose output would otherwise land in the middle of the test report
+    static MUTED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Throw away everything written from this thread
+#[cfg(test)]
+pub fn mute_thread() {
+    MUTED.set(true);
+}
+
+/// Write text to the terminal, through the serial line when there is one
+fn transmit(serial: &mut Option<SerialLine>, text: &str) -> io::Result<()> {
+    #[cfg(test)]
+    if MUTED.get() {
+        return Ok(());
+    }
+    // Raw mode turns off the terminal's own "\n" to "\r\n" translation
+    let text = if terminal::is_raw_mode_enabled().unwrap_or(false) {
+        Cow::Owned(to_crlf(text))
+    } else {
+        Cow::Borrowed(text)
+    };
+    let text = text.as_ref();
+    match serial {
+        Some(serial) => serial.transmit(text, &mut io::stdout().lock()),
+        None => io::stdout().lock().write_all(text.as_bytes()),
+    }
+}
+
+/// Start capturing all output into an asciicast v2 file
+pub fn start_recording(path: &Path) -> io::Result<()> {
+    let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+    let recorder = Recorder::create(path, width, height)?;
+    OUTPUT.lock().unwrap().recorder = Some(recorder);
+    Ok(())
+}
+
+/// Pace all output through a simulated serial line
+pub fn set_serial_line(serial: SerialLine) {
+    OUTPUT.lock().unwrap().serial = Some(serial);
+}
+
+/// Run terminal output through CRT glitch effects
+pub fn set_crt(crt: Crt) {
+    OUTPUT.lock().unwrap().crt = Some(crt);
+}
+
+/// Maybe roll the picture between stages; does nothing without CRT effects
+pub fn roll() -> io::Result<()> {
+    let mut output = OUTPUT.lock().unwrap();
+    let Output {
+        crt,
+        serial,
+        dashboard,
+        ..
+    } = &mut *output;
+    let Some(crt) = crt else {
+        return Ok(());
+    };
+
+    crt.roll(dashboard.is_some(), &mut |piece| transmit(serial, piece))?;
+    if let Some(dashboard) = dashboard {
+        dashboard.invalidate();
+        let frame = dashboard.render();
+        transmit(serial, &frame)?;
+    }
+    drop(output);
+    flush()
+}
+
+/// Switch to the alternate screen and route all output through the dashboard
+pub fn start_dashboard() -> io::Result<()> {
+    terminal::enable_raw_mode()?;
+    let mut setup = Vec::new();
+    queue!(setup, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let mut output = OUTPUT.lock().unwrap();
+    output.emit(&String::from_utf8_lossy(&setup))?;
+    let mut dashboard = Dashboard::new();
+    let frame = dashboard.render();
+    output.dashboard = Some(dashboard);
+    output.emit(&frame)?;
+    drop(output);
+    flush()
+}
+
+/// Leave the dashboard and restore the normal scrolling terminal
+pub fn stop_dashboard() -> io::Result<()> {
+    let mut output = OUTPUT.lock().unwrap();
+    if output.dashboard.take().is_none() {
+        return Ok(());
+    }
+
+    let mut teardown = Vec::new();
+    queue!(teardown, cursor::Show, terminal::LeaveAlternateScreen)?;
+    output.emit(&String::from_utf8_lossy(&teardown))?;
+    drop(output);
+    flush()?;
+    terminal::disable_raw_mode()
+}
+
+/// Reserve the bottom row of the terminal for a status line
+pub fn start_status_line() -> io::Result<()> {
+    let mut output = OUTPUT.lock().unwrap();
+    let mut status_line = StatusLine::new();
+    let setup = status_line.setup();
+    output.status_line = Some(status_line);
+    output.overlay(&setup)?;
+    output.refresh_status_line(true)?;
+    drop(output);
+    flush()
+}
+
+/// Remove the status line and let output use the whole terminal again
+pub fn stop_status_line() -> io::Result<()> {
+    let mut output = OUTPUT.lock().unwrap();
+    let Some(status_line) = output.status_line.take() else {
+        return Ok(());
+    };
+    output.overlay(&status_line.teardown())?;
+    drop(output);
+    flush()
+}
+
+/// Update the run status shown by the dashboard or status line and redraw it;
+/// does nothing when neither is on
+pub fn update_run_status(update: impl FnOnce(&mut RunStatus)) -> io::Result<()> {
+    let mut output = OUTPUT.lock().unwrap();
+    if let Some(dashboard) = &mut output.dashboard {
+        update(dashboard.run_status());
+        let frame = dashboard.render();
+        output.emit(&frame)?;
+    } else if let Some(status_line) = &mut output.status_line {
+        update(status_line.run_status());
+        output.refresh_status_line(true)?;
+    }
+    drop(output);
+    flush()
+}
+
+/// Hide the installer behind another screen while `show` runs, then put its screen
+/// back as it was. The cover goes straight to the terminal: it isn't recorded,
+/// paced or glitched
+pub fn cover<T>(show: impl FnOnce(&mut io::StdoutLock) -> io::Result<T>) -> io::Result<T> {
+    let mut output = OUTPUT.lock().unwrap();
+    let mut stdout = io::stdout().lock();
+    if output.dashboard.is_none() {
+        queue!(stdout, terminal::EnterAlternateScreen)?;
+    }
+    // Whole screen for the cover, whatever scroll region the status line set
+    queue!(
+        stdout,
+        Print("\x1b[r"),
+        terminal::Clear(terminal::ClearType::All),
+        cursor::MoveTo(0, 0)
+    )?;
+    let result = show(&mut stdout);
+
+    if let Some(dashboard) = &mut output.dashboard {
+        dashboard.invalidate();
+        let frame = to_crlf(&dashboard.render());
+        queue!(
+            stdout,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::Hide,
+            Print(frame)
+        )?;
+    } else {
+        queue!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+        if let Some(status_line) = &mut output.status_line {
+            let restore = status_line.restore() + &status_line.render(true);
+            queue!(stdout, Print(restore))?;
+        }
+    }
+    stdout.flush()?;
+    result
+}
+
+/// Show a short notice such as PAUSED at the cursor, or in the dashboard footer;
+/// `None` removes it
+pub fn notice(text: Option<&str>) -> io::Result<()> {
+    if ui::is_plain() {
+        return Ok(());
+    }
+
+    let mut output = OUTPUT.lock().unwrap();
+    if let Some(dashboard) = &mut output.dashboard {
+        dashboard.set_notice(text.map(str::to_string));
+        let frame = dashboard.render();
+        output.emit(&frame)?;
+    } else {
+        let frame = match text {
+            Some(text) => format!("\x1b7{}\x1b8", text.emphasis().reversed()),
+            None => "\x1b7\x1b[K\x1b8".to_string(),
+        };
+        output.overlay(&frame)?;
+    }
+    drop(output);
+    flush()
+}
+
+/// Write text to the terminal and any active recording
+pub fn write_str(text: &str) -> io::Result<()> {
+    OUTPUT.lock().unwrap().write(text)
+}
+
+/// Backend for the `out!` and `outln!` macros
+pub fn print(args: fmt::Arguments) {
+    if let Err(e) = write_str(&args.to_string()) {
+        // A hangup or a closed pipe ends the run like the signal it stands for
+        if let Some(signal) = crate::shutdown::write_failure(&e) {
+            crate::shutdown::exit(signal);
+        }
+        panic!("failed printing to stdout: {}", e);
+    }
+}
+
+pub fn flush() -> io::Result<()> {
+    io::stdout().flush()
+}
+
+/// Writer handle for crossterm commands that should go through the installer output
+pub fn stdout() -> Stdout {
+    Stdout
+}
+
+pub struct Stdout;
+
+impl Write for Stdout {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        write_str(&String::from_utf8_lossy(buf))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        flush()
+    }
+}
+
+/// Put a `\r` before every `\n` that lacks one, as a terminal in cooked mode would
+pub fn to_crlf(data: &str) -> String {
+    let mut translated = String::with_capacity(data.len());
+    let mut previous = None;
+    for c in data.chars() {
+        if c == '\n' && previous != Some('\r') {
+            translated.push('\r');
+        }
+        translated.push(c);
+        previous = Some(c);
+    }
+    translated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_crlf() {
+        assert_eq!(to_crlf("a\nb\r\nc"), "a\r\nb\r\nc");
+    }
+}
@@ -0,0 +1,52 @@
+use rand::rngs::StdRng;
+use rand::{Error, RngCore, SeedableRng};
+use std::sync::{LazyLock, Mutex};
+
+static SESSION_RNG: LazyLock<Mutex<StdRng>> = LazyLock::new(|| Mutex::new(StdRng::from_entropy()));
+
+/// Reseed the session generator so the whole run can be replayed
+pub fn seed(seed: u64) {
+    *SESSION_RNG.lock().unwrap() = StdRng::seed_from_u64(seed);
+}
+
+/// Get a handle to the session-wide random number generator
+pub fn session() -> SessionRng {
+    SessionRng
+}
+
+/// Handle to the single RNG shared by the installer, stages and log generators
+#[derive(Clone, Copy, Default)]
+pub struct SessionRng;
+
+impl RngCore for SessionRng {
+    fn next_u32(&mut self) -> u32 {
+        SESSION_RNG.lock().unwrap().next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        SESSION_RNG.lock().unwrap().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        SESSION_RNG.lock().unwrap().fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        SESSION_RNG.lock().unwrap().try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_same_seed_replays_sequence() {
+        seed(42);
+        let first: Vec<u32> = (0..8).map(|_| session().gen()).collect();
+        seed(42);
+        let second: Vec<u32> = (0..8).map(|_| session().gen()).collect();
+        assert_eq!(first, second);
+    }
+}
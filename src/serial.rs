@@ -0,0 +1,138 @@
+use crate::clock;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// Rates accepted by `--baud`
+const BAUD_RATES: [u32; 4] = [300, 1200, 2400, 9600];
+
+/// Bits on the wire per character: start bit, 8 data bits, stop bit
+const BITS_PER_CHAR: u32 = 10;
+
+/// Shortest pause worth sleeping for; shorter waits are saved up and paid together
+const MIN_SLEEP: Duration = Duration::from_millis(2);
+
+/// Characters a noisy line shows in place of the real one
+const GARBAGE: &[u8] = b"#$%&*+/<=>?@[\\]^_{|}~";
+
+/// A simulated serial line between the installer and the terminal:
+/// characters trickle out at the baud rate and noise garbles a few of them
+pub struct SerialLine {
+    char_time: Duration,
+    noise: f64,
+    /// Transmission time not yet slept off
+    owed: Duration,
+}
+
+impl SerialLine {
+    /// A line running at `baud`, garbling each character with probability `noise`
+    pub fn new(baud: u32, noise: f64) -> Self {
+        Self {
+            char_time: Duration::from_secs(BITS_PER_CHAR as u64) / baud,
+            noise,
+            owed: Duration::ZERO,
+        }
+    }
+
+    /// Send `text` to `out`, pacing every byte, including escapes and `\r` redraws
+    pub fn transmit(&mut self, text: &str, out: &mut impl Write) -> io::Result<()> {
+        // Noise uses its own generator so seeded sessions still replay identically
+        let mut rng = rand::thread_rng();
+        let mut chars = text.chars();
+
+        while let Some(c) = chars.next() {
+            let mut unit = String::from(c);
+            if c == '\x1b' {
+                // Escape sequences go out whole; a garbled one would wreck the screen
+                if let Some(next) = chars.next() {
+                    unit.push(next);
+                    if next == '[' {
+                        for c in chars.by_ref() {
+                            unit.push(c);
+                            if ('@'..='~').contains(&c) {
+                                break;
+                            }
+                        }
+                    }
+                }
+            } else if self.noise > 0.0 && c.is_ascii_graphic() && rng.gen_bool(self.noise) {
+                let garbage = *GARBAGE.choose(&mut rng).unwrap() as char;
+                write!(out, "{}", garbage)?;
+                self.wait(1, out)?;
+                unit = format!("\x08{}", c);
+            }
+
+            out.write_all(unit.as_bytes())?;
+            self.wait(unit.len(), out)?;
+        }
+        out.flush()
+    }
+
+    /// Account for `bytes` on the wire, sleeping once enough time has built up
+    fn wait(&mut self, bytes: usize, out: &mut impl Write) -> io::Result<()> {
+        self.owed += self.char_time * bytes as u32;
+        if self.owed >= MIN_SLEEP {
+            out.flush()?;
+            clock::sleep(self.owed);
+            self.owed = Duration::ZERO;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a `--baud` value: one of 300, 1200, 2400 or 9600
+pub fn parse_baud(value: &str) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(baud) if BAUD_RATES.contains(&baud) => Ok(baud),
+        _ => Err(format!(
+            "invalid baud rate '{}', expected 300, 1200, 2400 or 9600",
+            value
+        )),
+    }
+}
+
+/// Parse a `--line-noise` value: the chance from 0 to 1 that a character is garbled
+pub fn parse_noise(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(noise) if (0.0..=1.0).contains(&noise) => Ok(noise),
+        _ => Err(format!(
+            "invalid line noise '{}', expected a chance between 0 and 1",
+            value
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_baud() {
+        assert_eq!(parse_baud("2400"), Ok(2400));
+        assert!(parse_baud("4800").is_err());
+        assert!(parse_baud("fast").is_err());
+    }
+
+    #[test]
+    fn test_clean_line_passes_text_through() {
+        let mut out = Vec::new();
+        let text = "\x1b[32mok\x1b[0m\r50%\n";
+        SerialLine::new(9600, 0.0).transmit(text, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), text);
+    }
+
+    #[test]
+    fn test_noise_is_overwritten() {
+        let mut out = Vec::new();
+        SerialLine::new(9600, 1.0)
+            .transmit("ab\x1b[0m", &mut out)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let garbled: Vec<char> = out.chars().collect();
+        assert_eq!(garbled.len(), 10);
+        assert_eq!(&garbled[1..3], ['\x08', 'a']);
+        assert_eq!(&garbled[4..6], ['\x08', 'b']);
+        assert!(out.ends_with("\x1b[0m"));
+    }
+}
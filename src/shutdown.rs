@@ -0,0 +1,171 @@
+use crate::input::{self, Interrupt};
+use crate::theme::Themed;
+use std::io;
+use std::process;
+use std::sync::atomic::{AtomicI32, Ordering};
+#[cfg(unix)]
+use std::time::Duration;
+
+/// Signal number Ctrl+C stands for, also when it arrives as a key press in raw mode
+pub const SIGINT: i32 = 2;
+
+/// Exit as if by SIGHUP when the terminal is gone
+const SIGHUP: i32 = 1;
+
+/// Exit as if by SIGPIPE when whatever reads the output has gone away
+const SIGPIPE: i32 = 13;
+
+/// First terminating signal received, or 0
+static SIGNAL: AtomicI32 = AtomicI32::new(0);
+
+/// How long the installer gets to unwind before the terminal is reset from here
+#[cfg(unix)]
+const GRACE: Duration = Duration::from_secs(3);
+
+/// Route SIGTERM, SIGHUP and SIGINT into the same cancellation Ctrl+C triggers, so
+/// the installer unwinds and restores the terminal before `exit` says goodbye
+#[cfg(unix)]
+pub fn install() -> std::io::Result<()> {
+    use signal_hook::consts::SIGTERM;
+    use signal_hook::iterator::Signals;
+    use std::thread;
+    use std::time::Instant;
+
+    let mut signals = Signals::new([SIGTERM, SIGHUP, SIGINT])?;
+    thread::spawn(move || {
+        if let Some(signal) = signals.forever().next() {
+            SIGNAL.store(signal, Ordering::SeqCst);
+            input::raise(Interrupt::Cancel);
+            let received = Instant::now();
+            let mut repeated = false;
+            while !should_force_exit(received.elapsed(), repeated) {
+                repeated |= signals.pending().next().is_some();
+                thread::sleep(Duration::from_millis(50));
+            }
+            // Still running: something is stuck, or the user insists, so clean up without it
+            force_exit(signal);
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn install() -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Whether to stop waiting for the installer to unwind, `elapsed` after the first
+/// signal; a second signal means the user is done waiting too
+#[cfg(unix)]
+fn should_force_exit(elapsed: Duration, repeated: bool) -> bool {
+    repeated || elapsed >= GRACE
+}
+
+/// The shell's exit status for a process ended by `signal`
+fn exit_code(signal: i32) -> i32 {
+    128 + signal
+}
+
+/// The signal that is ending the run, if one arrived
+pub fn signal() -> Option<i32> {
+    match SIGNAL.load(Ordering::SeqCst) {
+        0 => None,
+        signal => Some(signal),
+    }
+}
+
+/// The signal a failed write stands for: the one already received, or the one the
+/// shell would report for a closed pipe (EPIPE) or a vanished terminal (EIO)
+pub fn write_failure(error: &io::Error) -> Option<i32> {
+    /// `EIO` on Linux and the BSDs
+    const EIO: i32 = 5;
+
+    signal().or(if error.kind() == io::ErrorKind::BrokenPipe {
+        Some(SIGPIPE)
+    } else if error.raw_os_error() == Some(EIO) {
+        Some(SIGHUP)
+    } else {
+        None
+    })
+}
+
+fn banner() -> String {
+    format!(
+        "\n\n{}\n{}\n{}\n{}\n",
+        "═══════════════════════════════════════".header(),
+        "Installation cancelled by user.".emphasis(),
+        "Thank you for using Universal System Installer!".emphasis(),
+        "═══════════════════════════════════════".header()
+    )
+}
+
+/// The one way out of a cancelled run, once the installer has put the terminal back:
+/// print the farewell and exit with the shell's code for `signal`
+pub fn exit(signal: i32) -> ! {
+    // The terminal may be gone already (SIGHUP), so a failed write is no reason to panic
+    let _ = crate::output::write_str(&banner());
+    let _ = crate::output::flush();
+    process::exit(exit_code(signal))
+}
+
+/// Reset the terminal behind the installer's back and leave
+#[cfg(unix)]
+fn force_exit(signal: i32) -> ! {
+    use crossterm::{
+        cursor, queue,
+        style::{Print, ResetColor},
+        terminal,
+    };
+    use std::fs::File;
+    use std::io::Write;
+    use std::mem::ManuallyDrop;
+    use std::os::fd::FromRawFd;
+
+    let _ = terminal::disable_raw_mode();
+    // The installer may be holding stdout, so write to the descriptor directly
+    // SAFETY: fd 1 stays open for the life of the process and is never closed here
+    let mut stdout = ManuallyDrop::new(unsafe { File::from_raw_fd(1) });
+    let _ = queue!(
+        stdout,
+        ResetColor,
+        Print("\x1b[r"),
+        cursor::Show,
+        terminal::LeaveAlternateScreen,
+        Print(banner())
+    );
+    let _ = stdout.flush();
+    process::exit(exit_code(signal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code_follows_the_shell() {
+        assert_eq!(exit_code(SIGINT), 130);
+        assert_eq!(exit_code(1), 129);
+        assert_eq!(exit_code(15), 143);
+    }
+
+    #[test]
+    fn test_write_failures_map_to_signals() {
+        let pipe = io::Error::from(io::ErrorKind::BrokenPipe);
+        assert_eq!(write_failure(&pipe), Some(SIGPIPE));
+        assert_eq!(
+            write_failure(&io::Error::from_raw_os_error(5)),
+            Some(SIGHUP)
+        );
+        let other = io::Error::other("disk full");
+        assert_eq!(write_failure(&other), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_force_exit_after_grace_or_second_signal() {
+        assert!(!should_force_exit(Duration::ZERO, false));
+        assert!(!should_force_exit(GRACE - Duration::from_millis(1), false));
+        assert!(should_force_exit(GRACE, false));
+        assert!(should_force_exit(Duration::ZERO, true));
+    }
+}
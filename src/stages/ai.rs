@@ -1,11 +1,12 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::config::AiConfig;
 use crate::log_generator::LogGenerator;
-use crate::ui::{ProgressBar, ProgressStyle};
+use crate::theme::Themed;
+use crate::ui::{ProgressBar, ProgressCurve, ProgressStyle};
 use colored::*;
 use rand::Rng;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
 pub struct AiStage {
@@ -13,92 +14,107 @@ pub struct AiStage {
 }
 
 impl AiStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "ai",
+        name: "AI Model Loading",
+        description: "AI model loading",
+        category: Category::Infrastructure,
+        estimated_duration: Duration::from_secs(16),
+        tags: &["modern"],
+    };
+
     pub fn new(config: AiConfig) -> Self {
         Self { config }
     }
 }
 
 impl InstallationStage for AiStage {
-    fn name(&self) -> &'static str {
-        "AI Model Loading"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_magenta().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
-        println!(
+        outln!(
             "{} Initializing HuggingFace Hub client...",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
-        thread::sleep(Duration::from_millis(600));
+        clock::sleep(Duration::from_millis(600));
 
         let model_name = "Llama-3-70B-Instruct-v1";
-        println!(
+        outln!(
             "{} Found model {} (size: 140GB)",
-            LogGenerator::timestamp().dimmed(),
-            model_name.cyan()
+            LogGenerator::timestamp().timestamp(),
+            model_name.accent()
         );
 
         if rng.gen_bool(self.config.failure_rate_network) {
-            println!(
+            outln!(
                 "{} Error: HuggingFace Hub: 502 Bad Gateway",
-                LogGenerator::timestamp().red()
+                LogGenerator::timestamp().error()
             );
-            println!(
+            outln!(
                 "{} Retrying connection in 3s...",
-                LogGenerator::timestamp().yellow()
+                LogGenerator::timestamp().warning()
             );
-            thread::sleep(Duration::from_millis(3000));
-            println!(
+            clock::sleep(Duration::from_millis(3000));
+            outln!(
                 "{} Connection established.",
-                LogGenerator::timestamp().dimmed()
+                LogGenerator::timestamp().timestamp()
             );
         }
 
-        println!(
+        outln!(
             "{} Downloading model weights...",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
-        let progress = ProgressBar::new(ProgressStyle::Block);
+        let speed_mb: u64 = rng.gen_range(80..400);
+        let progress = ProgressBar::new(ProgressStyle::Block)
+            .with_transfer(140 << 30, speed_mb << 20)
+            .with_eta()
+            .with_curve(ProgressCurve::Stall);
         progress.animate(
             "Downloading",
             rng.gen_range(self.config.model_download_speed_range.clone()),
             exit_check,
         )?;
 
-        println!(
+        outln!(
             "{} Verifying SHA256 checksums...",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
-        thread::sleep(Duration::from_millis(
+        clock::sleep(Duration::from_millis(
             rng.gen_range(self.config.checksum_delay_range.clone()),
         ));
         if rng.gen_bool(self.config.failure_rate_checksum) {
-            println!(
+            outln!(
                 "{} Warning: Checksum mismatch for shard 03, re-downloading...",
-                LogGenerator::timestamp().yellow()
+                LogGenerator::timestamp().warning()
             );
-            thread::sleep(Duration::from_millis(1000));
+            clock::sleep(Duration::from_millis(1000));
         }
-        println!(
+        outln!(
             "{} Integrity check passed.",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
 
-        println!(
+        outln!(
             "{} Initializing CUDA context...",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
 
-        println!(
+        outln!(
             "{} Compiling custom CUDA kernels (FlashAttention-v2)...",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
-        let progress = ProgressBar::new(ProgressStyle::Block);
+        let progress = ProgressBar::new(ProgressStyle::Block)
+            .with_eta()
+            .with_curve(ProgressCurve::Regress);
         progress.animate(
             "Compiling",
             rng.gen_range(self.config.compilation_speed_range.clone()),
@@ -106,32 +122,32 @@ impl InstallationStage for AiStage {
         )?;
 
         if rng.gen_bool(self.config.failure_rate_kernel_panic) {
-            println!(
+            outln!(
                 "{} Error: illegal memory access in kernel 'fused_rotary_embedding'",
-                LogGenerator::timestamp().red()
+                LogGenerator::timestamp().error()
             );
-            println!(
+            outln!(
                 "{} Resetting CUDA context and recompiling...",
-                LogGenerator::timestamp().yellow()
+                LogGenerator::timestamp().warning()
             );
-            thread::sleep(Duration::from_millis(2000));
+            clock::sleep(Duration::from_millis(2000));
         }
 
-        println!(
+        outln!(
             "{} Allocating tensors...",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
 
         if rng.gen_bool(self.config.failure_rate_oom) {
-            println!(
+            outln!(
                 "{} Error: CUDA out of memory. Tried to allocate 24.5GB",
-                LogGenerator::timestamp().red()
+                LogGenerator::timestamp().error()
             );
-            println!(
+            outln!(
                 "{} Reducing batch size to 1 and offloading optimizer state...",
-                LogGenerator::timestamp().yellow()
+                LogGenerator::timestamp().warning()
             );
-            thread::sleep(Duration::from_millis(1500));
+            clock::sleep(Duration::from_millis(1500));
         }
 
         let layers = 12;
@@ -145,30 +161,33 @@ impl InstallationStage for AiStage {
             } else {
                 "Attention"
             };
-            println!(
+            outln!(
                 "{} Loading layer {}/{} ({})...",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 i,
                 layers,
-                layer_type.cyan()
+                layer_type.accent()
             );
 
-            thread::sleep(Duration::from_millis(
+            clock::sleep(Duration::from_millis(
                 rng.gen_range(self.config.layer_load_delay_range.clone()),
             ));
         }
 
-        println!(
+        outln!(
             "{} Model loaded successfully.",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
+        );
+        outln!(
+            "{} Quantization: INT8",
+            LogGenerator::timestamp().timestamp()
         );
-        println!("{} Quantization: INT8", LogGenerator::timestamp().dimmed());
-        println!(
+        outln!(
             "{} Inference engine ready.",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
 
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
         Ok(())
     }
 }
@@ -1,11 +1,13 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::config::BiosConfig;
-use crate::ui::{ProgressBar, ProgressStyle, Spinner};
+use crate::output;
+use crate::theme::Themed;
+use crate::ui::{self, ProgressBar, ProgressStyle, Prompt, Spinner, SpinnerStyle};
 use chrono::Local;
 use colored::*;
 use rand::Rng;
-use std::io::{self, Write};
-use std::thread;
+use std::io;
 use std::time::Duration;
 use sysinfo::System;
 
@@ -25,6 +27,15 @@ struct SystemInfo {
 }
 
 impl BiosStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "bios",
+        name: "BIOS/Firmware Update Sequence",
+        description: "BIOS initialization",
+        category: Category::Firmware,
+        estimated_duration: Duration::from_secs(35),
+        tags: &["retro", "hardware"],
+    };
+
     pub fn new(config: BiosConfig) -> Self {
         Self { config }
     }
@@ -70,15 +81,15 @@ impl BiosStage {
 }
 
 impl InstallationStage for BiosStage {
-    fn name(&self) -> &'static str {
-        "BIOS/Firmware Update Sequence"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
         let sys_info = Self::get_system_info();
 
         let now = Local::now();
@@ -90,47 +101,38 @@ impl InstallationStage for BiosStage {
             rng.gen::<u16>()
         );
 
-        println!(
-            "{}",
-            "╔═══════════════════════════════════════════════════════════════╗".bright_cyan()
-        );
-        println!(
-            "{}",
-            format!("║  {:<61}║", self.config.vendor).bright_cyan()
-        );
-        println!(
+        outln!(
             "{}",
-            format!("║  {:<61}║", self.config.version).bright_cyan()
+            "╔═══════════════════════════════════════════════════════════════╗".header()
         );
-        println!(
+        outln!("{}", format!("║  {:<61}║", self.config.vendor).header());
+        outln!("{}", format!("║  {:<61}║", self.config.version).header());
+        outln!(
             "{}",
-            "╚═══════════════════════════════════════════════════════════════╝".bright_cyan()
+            "╚═══════════════════════════════════════════════════════════════╝".header()
         );
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{}",
-            format!("BIOS Date: {}  S/N: {}", self.config.bios_date, bios_serial).dimmed()
+            format!("BIOS Date: {}  S/N: {}", self.config.bios_date, bios_serial).dim()
         );
-        println!(
+        outln!(
             "{}",
             format!(
                 "System Date: {}  Time: {}",
                 now.format("%m/%d/%Y"),
                 now.format("%H:%M:%S")
             )
-            .dimmed()
+            .dim()
         );
-        println!("{}", format!("System Name: {}", sys_info.hostname).dimmed());
-        thread::sleep(Duration::from_millis(self.config.header_delay));
+        outln!("{}", format!("System Name: {}", sys_info.hostname).dim());
+        clock::sleep(Duration::from_millis(self.config.header_delay));
 
-        println!();
-        println!(
-            "{}",
-            "Performing POST (Power-On Self Test)...".bright_white()
-        );
-        thread::sleep(Duration::from_millis(self.config.post_start_delay));
+        outln!();
+        outln!("{}", "Performing POST (Power-On Self Test)...".emphasis());
+        clock::sleep(Duration::from_millis(self.config.post_start_delay));
 
-        let mut spinner = Spinner::new();
+        let mut spinner = Spinner::with_style(SpinnerStyle::Dos);
 
         spinner.animate(
             &format!("CPU: {}", sys_info.cpu_brand),
@@ -152,12 +154,14 @@ impl InstallationStage for BiosStage {
             )?;
         }
 
-        println!();
+        outln!();
         let memory_mb = sys_info.total_memory_kb / 1024;
         let memory_gb = memory_mb as f64 / 1024.0;
 
-        print!("{}", "Testing Memory: ".bright_white());
-        io::stdout().flush()?;
+        if !ui::is_plain() {
+            out!("{}", "Testing Memory: ".emphasis());
+            output::flush()?;
+        }
 
         let mem_progress = ProgressBar::new(ProgressStyle::Hash);
         let steps = 40;
@@ -169,16 +173,23 @@ impl InstallationStage for BiosStage {
             }
             let progress = i as f32 / steps as f32;
             let tested_kb = (sys_info.total_memory_kb as f32 * progress) as u64;
-            print!(
-                "\rTesting Memory: {} {}/{} KB",
-                mem_progress.render(progress),
-                tested_kb,
-                sys_info.total_memory_kb
-            );
-            io::stdout().flush()?;
-            thread::sleep(Duration::from_millis(delay));
+            ui::redraw_line(
+                &format!(
+                    "Testing Memory: {} {}/{} KB",
+                    mem_progress.render(progress),
+                    tested_kb,
+                    sys_info.total_memory_kb
+                ),
+                i,
+                steps,
+            )?;
+            clock::sleep(Duration::from_millis(delay));
+        }
+        if ui::is_plain() {
+            outln!("Testing Memory: OK");
+        } else {
+            outln!(" {}", "OK".success());
         }
-        println!(" {}", "OK".bright_green());
 
         spinner.animate(
             &format!(
@@ -190,42 +201,47 @@ impl InstallationStage for BiosStage {
         )?;
 
         if rng.gen_bool(self.config.cmos_error_chance) {
-            println!(
+            outln!(
                 "{}",
-                "WARNING: CMOS checksum invalid, loading defaults".yellow()
+                "WARNING: CMOS checksum invalid, loading defaults".warning()
             );
-            thread::sleep(Duration::from_millis(self.config.cmos_warning_time));
+            clock::sleep(Duration::from_millis(self.config.cmos_warning_time));
+            Prompt::any_key("Press F1 to continue, DEL to enter SETUP")
+                .brief()
+                .ask(exit_check)?;
         }
 
-        println!();
-        println!("{}", "Detecting IDE Devices...".bright_white());
+        outln!();
+        outln!("{}", "Detecting IDE Devices...".emphasis());
 
-        print!("  Primary Master   [0x1F0-0x1F7]: ");
-        io::stdout().flush()?;
-        thread::sleep(Duration::from_millis(self.config.ide_master_time));
-        println!("{}", "WDC WD2000JB-00GVC0".bright_green());
+        out!("  Primary Master   [0x1F0-0x1F7]: ");
+        output::flush()?;
+        clock::sleep(Duration::from_millis(self.config.ide_master_time));
+        outln!("{}", "WDC WD2000JB-00GVC0".success());
 
-        print!("  Primary Slave    [0x1F0-0x1F7]: ");
-        io::stdout().flush()?;
-        thread::sleep(Duration::from_millis(self.config.ide_slave_time));
-        println!("{}", "None".dimmed());
+        out!("  Primary Slave    [0x1F0-0x1F7]: ");
+        output::flush()?;
+        clock::sleep(Duration::from_millis(self.config.ide_slave_time));
+        outln!("{}", "None".dim());
 
-        print!("  Secondary Master [0x170-0x177]: ");
-        io::stdout().flush()?;
-        thread::sleep(Duration::from_millis(self.config.ide_master_time));
-        println!("{}", "ATAPI CD-ROM".bright_green());
+        out!("  Secondary Master [0x170-0x177]: ");
+        output::flush()?;
+        clock::sleep(Duration::from_millis(self.config.ide_master_time));
+        outln!("{}", "ATAPI CD-ROM".success());
 
-        print!("  Secondary Slave  [0x170-0x177]: ");
-        io::stdout().flush()?;
-        thread::sleep(Duration::from_millis(self.config.ide_slave_time));
-        println!("{}", "None".dimmed());
+        out!("  Secondary Slave  [0x170-0x177]: ");
+        output::flush()?;
+        clock::sleep(Duration::from_millis(self.config.ide_slave_time));
+        outln!("{}", "None".dim());
 
-        println!();
-        println!("{}", "Scanning PCI bus...".bright_white());
+        outln!();
+        outln!("{}", "Scanning PCI bus...".emphasis());
 
         let pci_progress = ProgressBar::new(ProgressStyle::Block);
-        print!("  Probing 00:00.0 - 00:1F.7: ");
-        io::stdout().flush()?;
+        if !ui::is_plain() {
+            out!("  Probing 00:00.0 - 00:1F.7: ");
+            output::flush()?;
+        }
 
         let steps = 30;
         let delay = self.config.pci_scan_time / steps;
@@ -234,29 +250,31 @@ impl InstallationStage for BiosStage {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
             let progress = i as f32 / steps as f32;
-            print!(
-                "\r  Probing 00:00.0 - 00:1F.7: {}",
-                pci_progress.render(progress)
-            );
-            io::stdout().flush()?;
-            thread::sleep(Duration::from_millis(delay));
+            ui::redraw_line(
+                &format!(
+                    "  Probing 00:00.0 - 00:1F.7: {}",
+                    pci_progress.render(progress)
+                ),
+                i,
+                steps,
+            )?;
+            clock::sleep(Duration::from_millis(delay));
+        }
+        if !ui::is_plain() {
+            outln!();
         }
-        println!();
 
-        thread::sleep(Duration::from_millis(200));
+        clock::sleep(Duration::from_millis(200));
         let pci_addr1 = format!("00:{:02X}.0", rng.gen_range(0x02..0x10));
         let pci_addr2 = format!("00:{:02X}.0", rng.gen_range(0x10..0x1F));
-        println!(
-            "  Found {} - VGA Compatible Controller",
-            pci_addr1.bright_cyan()
-        );
-        thread::sleep(Duration::from_millis(self.config.pci_device_time));
-        println!("  Found {} - Ethernet Controller", pci_addr2.bright_cyan());
-        thread::sleep(Duration::from_millis(self.config.pci_device_time));
-        println!("  Found {} - SMBus Controller", "00:1F.3".bright_cyan());
-        thread::sleep(Duration::from_millis(self.config.pci_device_time));
-
-        println!();
+        outln!("  Found {} - VGA Compatible Controller", pci_addr1.accent());
+        clock::sleep(Duration::from_millis(self.config.pci_device_time));
+        outln!("  Found {} - Ethernet Controller", pci_addr2.accent());
+        clock::sleep(Duration::from_millis(self.config.pci_device_time));
+        outln!("  Found {} - SMBus Controller", "00:1F.3".accent());
+        clock::sleep(Duration::from_millis(self.config.pci_device_time));
+
+        outln!();
         spinner.animate(
             &format!("Network Adapters: {} detected", sys_info.network_count),
             self.config.network_detect_time,
@@ -273,7 +291,7 @@ impl InstallationStage for BiosStage {
             exit_check,
         )?;
 
-        println!();
+        outln!();
         spinner.animate(
             &format!("Host OS: {}", sys_info.os_name),
             self.config.system_info_time,
@@ -299,33 +317,33 @@ impl InstallationStage for BiosStage {
             exit_check,
         )?;
 
-        println!();
+        outln!();
         spinner.animate(
             "Boot Device Priority:",
             self.config.boot_priority_time,
             exit_check,
         )?;
-        println!("  1st: {}", "Hard Disk Drive".bright_green());
-        println!("  2nd: {}", "CD-ROM Drive".dimmed());
-        println!("  3rd: {}", "Network Boot".dimmed());
-        thread::sleep(Duration::from_millis(self.config.boot_display_time));
+        outln!("  1st: {}", "Hard Disk Drive".success());
+        outln!("  2nd: {}", "CD-ROM Drive".dim());
+        outln!("  3rd: {}", "Network Boot".dim());
+        clock::sleep(Duration::from_millis(self.config.boot_display_time));
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{}",
-            "═══════════════════════════════════════════════════════════════".bright_yellow()
+            "═══════════════════════════════════════════════════════════════".warning()
         );
-        println!(
+        outln!(
             "{}",
             "  CRITICAL: Firmware Update Sequence Initiated"
-                .bright_yellow()
+                .warning()
                 .bold()
         );
-        println!(
+        outln!(
             "{}",
-            "═══════════════════════════════════════════════════════════════".bright_yellow()
+            "═══════════════════════════════════════════════════════════════".warning()
         );
-        thread::sleep(Duration::from_millis(self.config.firmware_header_delay));
+        clock::sleep(Duration::from_millis(self.config.firmware_header_delay));
 
         spinner.animate(
             "Backing up current BIOS to NVRAM...",
@@ -338,21 +356,21 @@ impl InstallationStage for BiosStage {
             exit_check,
         )?;
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{}",
             "  WARNING: Do NOT power off or restart during this process!"
-                .yellow()
+                .warning()
                 .bold()
         );
-        println!(
+        outln!(
             "{}",
-            "  System damage may occur if interrupted!".yellow().bold()
+            "  System damage may occur if interrupted!".warning().bold()
         );
-        println!();
-        thread::sleep(Duration::from_millis(self.config.warning_delay));
+        outln!();
+        clock::sleep(Duration::from_millis(self.config.warning_delay));
 
-        let progress = ProgressBar::new(ProgressStyle::Block);
+        let progress = ProgressBar::new(ProgressStyle::Dos);
         progress.animate(
             "Erasing flash sectors:",
             rng.gen_range(self.config.erase_min..self.config.erase_max),
@@ -371,7 +389,7 @@ impl InstallationStage for BiosStage {
             exit_check,
         )?;
 
-        println!();
+        outln!();
         spinner.animate(
             "Firmware update complete!",
             self.config.complete_time,
@@ -383,21 +401,18 @@ impl InstallationStage for BiosStage {
             exit_check,
         )?;
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{}",
             format!(
                 "BIOS update successful - {} -> {}",
                 self.config.version, self.config.new_version
             )
-            .bright_green()
+            .success()
             .bold()
         );
-        println!(
-            "{}",
-            "System will initialize with new firmware".bright_green()
-        );
-        thread::sleep(Duration::from_millis(self.config.success_delay));
+        outln!("{}", "System will initialize with new firmware".success());
+        clock::sleep(Duration::from_millis(self.config.success_delay));
 
         Ok(())
     }
@@ -1,10 +1,11 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::config::BootConfig;
 use crate::kernel_logs::KernelLogs;
+use crate::theme::Themed;
 use colored::*;
 use rand::Rng;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
 pub struct BootStage {
@@ -13,6 +14,15 @@ pub struct BootStage {
 }
 
 impl BootStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "boot",
+        name: "Kernel Boot Sequence",
+        description: "Boot sequence",
+        category: Category::Boot,
+        estimated_duration: Duration::from_secs(5),
+        tags: &["linux"],
+    };
+
     pub fn new(config: BootConfig) -> Self {
         Self {
             config,
@@ -22,15 +32,15 @@ impl BootStage {
 }
 
 impl InstallationStage for BootStage {
-    fn name(&self) -> &'static str {
-        "Kernel Boot Sequence"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
         let log_count = rng.gen_range(self.config.log_count_range.clone());
         let logs = self.kernel_logs.random_batch(log_count);
@@ -40,14 +50,14 @@ impl InstallationStage for BootStage {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
 
-            println!("{}", log.dimmed());
-            thread::sleep(Duration::from_millis(
+            outln!("{}", log.dim());
+            clock::sleep(Duration::from_millis(
                 rng.gen_range(self.config.log_delay_range.clone()),
             ));
         }
 
-        println!();
-        thread::sleep(Duration::from_millis(self.config.final_delay));
+        outln!();
+        clock::sleep(Duration::from_millis(self.config.final_delay));
 
         Ok(())
     }
@@ -1,12 +1,13 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::config::BootloaderConfig;
 use crate::log_generator::LogGenerator;
-use crate::ui::{ProgressBar, ProgressStyle, Spinner};
+use crate::theme::Themed;
+use crate::ui::{ProgressBar, ProgressStyle, Prompt, Spinner};
 use colored::*;
 use rand::Rng;
 use std::env;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
 pub struct BootloaderStage {
@@ -14,57 +15,66 @@ pub struct BootloaderStage {
 }
 
 impl BootloaderStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "bootloader",
+        name: "Bootloader Installation",
+        description: "Bootloader installation",
+        category: Category::Boot,
+        estimated_duration: Duration::from_secs(25),
+        tags: &["linux"],
+    };
+
     pub fn new(config: BootloaderConfig) -> Self {
         Self { config }
     }
 }
 
 impl InstallationStage for BootloaderStage {
-    fn name(&self) -> &'static str {
-        "Bootloader Installation"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Installing GRUB2 bootloader...".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "Installing GRUB2 bootloader...".emphasis()
         );
-        thread::sleep(Duration::from_millis(self.config.install_delay));
+        clock::sleep(Duration::from_millis(self.config.install_delay));
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Running pre-installation checks...".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Running pre-installation checks...".dim()
         );
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
 
         let is_efi = rng.gen_bool(0.7);
         if is_efi {
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "EFI variables detected, installing for UEFI mode".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "EFI variables detected, installing for UEFI mode".dim()
             );
-            thread::sleep(Duration::from_millis(300));
-            println!(
+            clock::sleep(Duration::from_millis(300));
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "EFI System Partition found at /boot/efi".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "EFI System Partition found at /boot/efi".dim()
             );
         } else {
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Legacy BIOS mode detected".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "Legacy BIOS mode detected".dim()
             );
         }
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
         let mut spinner = Spinner::new();
         spinner.animate(
@@ -81,39 +91,39 @@ impl InstallationStage for BootloaderStage {
         ];
         let (device, dev_type, dev_name, capacity_gb) = devices[rng.gen_range(0..devices.len())];
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("Detected {} device: {} ({})", dev_type, dev_name, device).dimmed()
+            LogGenerator::timestamp().timestamp(),
+            format!("Detected {} device: {} ({})", dev_type, dev_name, device).dim()
         );
-        thread::sleep(Duration::from_millis(250));
-        println!(
+        clock::sleep(Duration::from_millis(250));
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("  Capacity: {} GB", capacity_gb).dimmed()
+            LogGenerator::timestamp().timestamp(),
+            format!("  Capacity: {} GB", capacity_gb).dim()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("  Block size: 512 bytes").dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "  Block size: 512 bytes".dim()
         );
-        thread::sleep(Duration::from_millis(300));
+        clock::sleep(Duration::from_millis(300));
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Analyzing partition table...".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Analyzing partition table...".dim()
         );
-        thread::sleep(Duration::from_millis(600));
+        clock::sleep(Duration::from_millis(600));
 
         let partition_table = if is_efi { "GPT" } else { "MBR" };
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("  Partition table type: {}", partition_table).dimmed()
+            LogGenerator::timestamp().timestamp(),
+            format!("  Partition table type: {}", partition_table).dim()
         );
-        thread::sleep(Duration::from_millis(250));
+        clock::sleep(Duration::from_millis(250));
 
         let boot_partition = if device == "/dev/nvme0n1" {
             format!("{}p2", device)
@@ -121,12 +131,12 @@ impl InstallationStage for BootloaderStage {
             format!("{}2", device)
         };
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("  Boot partition: {}", boot_partition).dimmed()
+            LogGenerator::timestamp().timestamp(),
+            format!("  Boot partition: {}", boot_partition).dim()
         );
-        thread::sleep(Duration::from_millis(250));
+        clock::sleep(Duration::from_millis(250));
 
         if is_efi {
             let efi_partition = if device == "/dev/nvme0n1" {
@@ -134,37 +144,51 @@ impl InstallationStage for BootloaderStage {
             } else {
                 format!("{}1", device)
             };
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                format!("  EFI partition: {} (FAT32, 512 MB)", efi_partition).dimmed()
+                LogGenerator::timestamp().timestamp(),
+                format!("  EFI partition: {} (FAT32, 512 MB)", efi_partition).dim()
             );
-            thread::sleep(Duration::from_millis(250));
+            clock::sleep(Duration::from_millis(250));
         }
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Checking filesystems...".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Checking filesystems...".dim()
         );
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("  {} is mounted on /", boot_partition).dimmed()
+            LogGenerator::timestamp().timestamp(),
+            format!("  {} is mounted on /", boot_partition).dim()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "  Filesystem: ext4".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "  Filesystem: ext4".dim()
         );
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
-        if exit_check() {
-            return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
+        outln!();
+        let question = format!(
+            "Install GRUB to {}? Its boot record will be overwritten.",
+            device
+        );
+        if !Prompt::yes_no(&question, true)
+            .brief()
+            .ask(exit_check)?
+            .is_yes()
+        {
+            outln!(
+                "{} {}",
+                LogGenerator::timestamp().timestamp(),
+                "Bootloader installation skipped. The system may not boot.".warning()
+            );
+            return Ok(());
         }
 
         let arch = env::consts::ARCH;
@@ -194,12 +218,12 @@ impl InstallationStage for BootloaderStage {
             }
         };
 
-        println!(
+        outln!(
             "\n{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("Installing for {} platform...", platform).bright_white()
+            LogGenerator::timestamp().timestamp(),
+            format!("Installing for {} platform...", platform).emphasis()
         );
-        thread::sleep(Duration::from_millis(self.config.device_install_delay));
+        clock::sleep(Duration::from_millis(self.config.device_install_delay));
 
         let modules = if is_efi {
             vec![
@@ -242,58 +266,58 @@ impl InstallationStage for BootloaderStage {
             ]
         };
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("Installing GRUB modules ({} modules)...", modules.len()).dimmed()
+            LogGenerator::timestamp().timestamp(),
+            format!("Installing GRUB modules ({} modules)...", modules.len()).dim()
         );
-        thread::sleep(Duration::from_millis(300));
+        clock::sleep(Duration::from_millis(300));
 
         for (idx, module) in modules.iter().enumerate() {
             if exit_check() {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
             if idx % 3 == 0 {
-                println!(
+                outln!(
                     "{} {}",
-                    LogGenerator::timestamp().dimmed(),
-                    format!("  Installing {}.mod", module).dimmed()
+                    LogGenerator::timestamp().timestamp(),
+                    format!("  Installing {}.mod", module).dim()
                 );
-                thread::sleep(Duration::from_millis(rng.gen_range(150..300)));
+                clock::sleep(Duration::from_millis(rng.gen_range(150..300)));
             }
         }
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Installing bootloader fonts...".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Installing bootloader fonts...".dim()
         );
-        thread::sleep(Duration::from_millis(300));
-        println!(
+        clock::sleep(Duration::from_millis(300));
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "  Converting DejaVu Sans Regular to PFF2 format".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "  Converting DejaVu Sans Regular to PFF2 format".dim()
         );
-        thread::sleep(Duration::from_millis(600));
+        clock::sleep(Duration::from_millis(600));
 
         if exit_check() {
             return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
         }
 
-        println!(
+        outln!(
             "\n{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Generating grub configuration file...".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "Generating grub configuration file...".emphasis()
         );
-        thread::sleep(Duration::from_millis(self.config.config_gen_delay));
+        clock::sleep(Duration::from_millis(self.config.config_gen_delay));
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Probing system configuration...".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Probing system configuration...".dim()
         );
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
 
         let kernel_sets = [
             [
@@ -314,47 +338,41 @@ impl InstallationStage for BootloaderStage {
         ];
         let kernels = &kernel_sets[rng.gen_range(0..kernel_sets.len())];
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Searching for linux images...".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Searching for linux images...".dim()
         );
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
         for kernel in kernels {
             if exit_check() {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                format!("Found linux image: /boot/{}", kernel).dimmed()
+                LogGenerator::timestamp().timestamp(),
+                format!("Found linux image: /boot/{}", kernel).dim()
             );
 
-            let initrd_suffix = if kernel.contains("generic") {
-                &kernel[8..]
-            } else if kernel.contains("amd64") {
-                &kernel[8..]
-            } else {
-                &kernel[8..]
-            };
+            let initrd_suffix = &kernel[8..];
 
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                format!("Found initrd image: /boot/initrd.img-{}", initrd_suffix).dimmed()
+                LogGenerator::timestamp().timestamp(),
+                format!("Found initrd image: /boot/initrd.img-{}", initrd_suffix).dim()
             );
-            thread::sleep(Duration::from_millis(
+            clock::sleep(Duration::from_millis(
                 rng.gen_range(self.config.kernel_scan_delay_range.clone()),
             ));
         }
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Found memtest86+ image: /boot/memtest86+.bin".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Found memtest86+ image: /boot/memtest86+.bin".dim()
         );
-        thread::sleep(Duration::from_millis(300));
+        clock::sleep(Duration::from_millis(300));
 
         if rng.gen_bool(self.config.windows_found_chance) {
             let partition = if device == "/dev/nvme0n1" {
@@ -362,89 +380,89 @@ impl InstallationStage for BootloaderStage {
             } else {
                 format!("{}3", device)
             };
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                format!("Found Windows Boot Manager on {}", partition).dimmed()
+                LogGenerator::timestamp().timestamp(),
+                format!("Found Windows Boot Manager on {}", partition).dim()
             );
-            thread::sleep(Duration::from_millis(250));
-            println!(
+            clock::sleep(Duration::from_millis(250));
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 "  Windows 11 (loader) (on {})"
                     .replace("{}", &partition)
-                    .dimmed()
+                    .dim()
             );
-            thread::sleep(Duration::from_millis(200));
-            println!(
+            clock::sleep(Duration::from_millis(200));
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Adding boot menu entry for Windows".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "Adding boot menu entry for Windows".dim()
             );
-            thread::sleep(Duration::from_millis(self.config.windows_delay));
+            clock::sleep(Duration::from_millis(self.config.windows_delay));
         }
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Writing configuration to /boot/grub/grub.cfg...".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Writing configuration to /boot/grub/grub.cfg...".dim()
         );
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "  Setting default boot entry: 0".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "  Setting default boot entry: 0".dim()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "  Setting timeout: 5 seconds".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "  Setting timeout: 5 seconds".dim()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "  Enabling submenu for older kernels".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "  Enabling submenu for older kernels".dim()
         );
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
 
         if rng.gen_bool(0.6) {
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Installing GRUB theme...".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "Installing GRUB theme...".dim()
             );
-            thread::sleep(Duration::from_millis(300));
-            println!(
+            clock::sleep(Duration::from_millis(300));
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "  Theme: starfield".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "  Theme: starfield".dim()
             );
-            thread::sleep(Duration::from_millis(400));
+            clock::sleep(Duration::from_millis(400));
         }
 
-        println!(
+        outln!(
             "\n{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Installing bootloader to disk...".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "Installing bootloader to disk...".emphasis()
         );
-        thread::sleep(Duration::from_millis(300));
+        clock::sleep(Duration::from_millis(300));
 
         if is_efi {
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Creating EFI boot entry...".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "Creating EFI boot entry...".dim()
             );
-            thread::sleep(Duration::from_millis(300));
-            println!(
+            clock::sleep(Duration::from_millis(300));
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "  EFI application: \\EFI\\ubuntu\\shimx64.efi".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "  EFI application: \\EFI\\ubuntu\\shimx64.efi".dim()
             );
-            thread::sleep(Duration::from_millis(400));
+            clock::sleep(Duration::from_millis(400));
         }
 
         for i in 0..5 {
@@ -456,7 +474,7 @@ impl InstallationStage for BootloaderStage {
             progress.animate(
                 &format!(
                     "{} Writing stage {} image...",
-                    LogGenerator::timestamp().dimmed(),
+                    LogGenerator::timestamp().timestamp(),
                     i + 1
                 ),
                 rng.gen_range(self.config.write_stage_delay_range.clone()),
@@ -464,50 +482,50 @@ impl InstallationStage for BootloaderStage {
             )?;
         }
 
-        println!(
+        outln!(
             "\n{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Verifying installation...".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Verifying installation...".dim()
         );
-        thread::sleep(Duration::from_millis(600));
+        clock::sleep(Duration::from_millis(600));
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "  Checking boot sector... OK".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "  Checking boot sector... OK".dim()
         );
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
         if is_efi {
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "  Checking EFI boot variables... OK".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "  Checking EFI boot variables... OK".dim()
             );
-            thread::sleep(Duration::from_millis(400));
+            clock::sleep(Duration::from_millis(400));
         }
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "  Verifying GRUB modules... OK".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "  Verifying GRUB modules... OK".dim()
         );
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "  Checking configuration file... OK".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "  Checking configuration file... OK".dim()
         );
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Installation finished. No error reported.".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "Installation finished. No error reported.".success()
         );
 
-        thread::sleep(Duration::from_millis(self.config.finish_delay));
+        clock::sleep(Duration::from_millis(self.config.finish_delay));
 
         Ok(())
     }
@@ -1,11 +1,12 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::config::CloudConfig;
 use crate::log_generator::LogGenerator;
+use crate::theme::Themed;
 use crate::ui::{ProgressBar, ProgressStyle};
 use colored::*;
 use rand::Rng;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
 pub struct CloudStage {
@@ -13,27 +14,36 @@ pub struct CloudStage {
 }
 
 impl CloudStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "cloud",
+        name: "Cloud Infrastructure Provisioning",
+        description: "Cloud provisioning",
+        category: Category::Infrastructure,
+        estimated_duration: Duration::from_secs(26),
+        tags: &["modern", "cloud"],
+    };
+
     pub fn new(config: CloudConfig) -> Self {
         Self { config }
     }
 }
 
 impl InstallationStage for CloudStage {
-    fn name(&self) -> &'static str {
-        "Cloud Infrastructure Provisioning"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_cyan().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
-        println!(
+        outln!(
             "{} Initializing Terraform backend...",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
-        thread::sleep(Duration::from_millis(600));
+        clock::sleep(Duration::from_millis(600));
 
         let resources = [
             ("aws_vpc.main", "VPC"),
@@ -57,83 +67,77 @@ impl InstallationStage for CloudStage {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
 
-            let colored_resource = match r_type {
-                "IAM Role" => resource.yellow(),
-                "EC2 Instance" | "Lambda" => resource.green(),
-                "RDS Instance" | "DynamoDB" => resource.blue(),
-                "S3 Bucket" | "CloudFront" => resource.magenta(),
-                _ => resource.cyan(),
-            };
+            let colored_resource = resource.accent();
 
-            println!(
+            outln!(
                 "{} Creating {} ({})",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 colored_resource,
-                r_type.dimmed()
+                r_type.dim()
             );
 
             if rng.gen_bool(self.config.failure_rate_rate_limit) {
-                thread::sleep(Duration::from_millis(rng.gen_range(200..500)));
-                println!(
+                clock::sleep(Duration::from_millis(rng.gen_range(200..500)));
+                outln!(
                     "{} Error: 429 Too Many Requests (RequestLimitExceeded)",
-                    LogGenerator::timestamp().red()
+                    LogGenerator::timestamp().error()
                 );
-                println!("{} Throttling...", LogGenerator::timestamp().yellow());
-                thread::sleep(Duration::from_millis(2000));
-                println!(
+                outln!("{} Throttling...", LogGenerator::timestamp().warning());
+                clock::sleep(Duration::from_millis(2000));
+                outln!(
                     "{} Resuming operation...",
-                    LogGenerator::timestamp().dimmed()
+                    LogGenerator::timestamp().timestamp()
                 );
             }
 
             if r_type == "EC2 Instance"
                 && rng.gen_bool(self.config.failure_rate_insufficient_capacity)
             {
-                thread::sleep(Duration::from_millis(1000));
-                println!("{} Error: InsufficientInstanceCapacity: We currently do not have sufficient capacity in the Availability Zone you requested.", LogGenerator::timestamp().red());
-                println!(
+                clock::sleep(Duration::from_millis(1000));
+                outln!("{} Error: InsufficientInstanceCapacity: We currently do not have sufficient capacity in the Availability Zone you requested.", LogGenerator::timestamp().error());
+                outln!(
                     "{} Retrying in different Availability Zone (us-east-1b)...",
-                    LogGenerator::timestamp().yellow()
+                    LogGenerator::timestamp().warning()
                 );
-                thread::sleep(Duration::from_millis(1500));
+                clock::sleep(Duration::from_millis(1500));
             }
 
             if r_type == "Lambda" && rng.gen_bool(self.config.failure_rate_dependency_violation) {
-                println!("{} Error: The role defined for the function cannot be assumed by the function.", LogGenerator::timestamp().red());
-                println!(
+                outln!("{} Error: The role defined for the function cannot be assumed by the function.", LogGenerator::timestamp().error());
+                outln!(
                     "{} Waiting for IAM propagation...",
-                    LogGenerator::timestamp().yellow()
+                    LogGenerator::timestamp().warning()
                 );
-                thread::sleep(Duration::from_millis(2500));
+                clock::sleep(Duration::from_millis(2500));
             }
 
             if r_type == "S3 Bucket" && rng.gen_bool(self.config.failure_rate_checksum_mismatch) {
-                println!(
+                outln!(
                     "{} Error: Checksum mismatch during upload.",
-                    LogGenerator::timestamp().red()
+                    LogGenerator::timestamp().error()
                 );
-                println!(
+                outln!(
                     "{} Re-calculating hashes and retrying...",
-                    LogGenerator::timestamp().yellow()
+                    LogGenerator::timestamp().warning()
                 );
-                thread::sleep(Duration::from_millis(1200));
+                clock::sleep(Duration::from_millis(1200));
             }
 
             let duration = rng.gen_range(self.config.provision_speed_range.clone());
             let progress = ProgressBar::new(ProgressStyle::Block);
             progress.animate("Provisioning", duration, exit_check)?;
 
-            println!(
+            outln!(
                 "{} Resource {} is Available",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 colored_resource
             );
         }
 
-        println!();
-        println!("{}", "Infrastructure provisioning complete.".bright_green());
+        outln!();
+        outln!("{}", "Infrastructure provisioning complete.".success());
 
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
         Ok(())
     }
 }
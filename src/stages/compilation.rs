@@ -1,18 +1,31 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
 use crate::build_logs::BuildLogs;
+use crate::clock;
 use crate::log_generator::LogGenerator;
-use crate::ui::{ProgressBar, ProgressStyle};
+use crate::theme::Themed;
+use crate::ui::{MultiProgress, ProgressBar, ProgressStyle};
 use colored::*;
 use rand::Rng;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
+/// Object files built side by side, like `make -j4`
+const PARALLEL_JOBS: usize = 4;
+
 pub struct CompilationStage {
     build_logs: BuildLogs,
 }
 
 impl CompilationStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "compilation",
+        name: "Kernel Module Compilation",
+        description: "Compilation",
+        category: Category::Build,
+        estimated_duration: Duration::from_secs(600),
+        tags: &["linux", "long"],
+    };
+
     pub fn new() -> Self {
         Self {
             build_logs: BuildLogs::load(),
@@ -21,23 +34,24 @@ impl CompilationStage {
 }
 
 impl InstallationStage for CompilationStage {
-    fn name(&self) -> &'static str {
-        "Kernel Module Compilation"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "make[1]: Entering directory '/usr/src/linux-headers-5.4.0'".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "make[1]: Entering directory '/usr/src/linux-headers-5.4.0'".dim()
         );
-        println!();
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
         let logs = self.build_logs.all_logs();
+        let mut jobs = MultiProgress::new();
 
         for log in logs {
             if exit_check() {
@@ -58,14 +72,17 @@ impl InstallationStage for CompilationStage {
                     rng.gen_range(600..1500)
                 };
 
-                let progress = ProgressBar::new(ProgressStyle::Block);
-                progress.animate(
-                    &format!("{} {}", LogGenerator::timestamp().dimmed(), log.cyan()),
+                jobs.add(
+                    format!("{} {}", LogGenerator::timestamp().timestamp(), log.accent()),
                     duration,
-                    exit_check,
-                )?;
+                    ProgressBar::new(ProgressStyle::Block),
+                );
+                if jobs.len() == PARALLEL_JOBS {
+                    std::mem::take(&mut jobs).animate(exit_check)?;
+                }
             } else {
-                println!("{} {}", LogGenerator::timestamp().dimmed(), log.cyan());
+                std::mem::take(&mut jobs).animate(exit_check)?;
+                outln!("{} {}", LogGenerator::timestamp().timestamp(), log.accent());
                 let speed_category = rng.gen_range(0..10);
                 let delay = if speed_category < 4 {
                     rng.gen_range(10..30)
@@ -74,15 +91,17 @@ impl InstallationStage for CompilationStage {
                 } else {
                     rng.gen_range(80..200)
                 };
-                thread::sleep(Duration::from_millis(delay));
+                clock::sleep(Duration::from_millis(delay));
             }
         }
 
-        println!();
-        println!(
+        jobs.animate(exit_check)?;
+
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "make[1]: Leaving directory '/usr/src/linux-headers-5.4.0'".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "make[1]: Leaving directory '/usr/src/linux-headers-5.4.0'".dim()
         );
 
         Ok(())
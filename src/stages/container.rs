@@ -1,11 +1,12 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::config::ContainerConfig;
 use crate::log_generator::LogGenerator;
-use crate::ui::{ProgressBar, ProgressStyle};
+use crate::theme::Themed;
+use crate::ui::{MultiProgress, ProgressBar, ProgressCurve, ProgressStyle};
 use colored::*;
 use rand::Rng;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
 pub struct ContainerStage {
@@ -13,21 +14,30 @@ pub struct ContainerStage {
 }
 
 impl ContainerStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "container",
+        name: "Container Orchestration",
+        description: "Container orchestration",
+        category: Category::Infrastructure,
+        estimated_duration: Duration::from_secs(45),
+        tags: &["modern", "cloud"],
+    };
+
     pub fn new(config: ContainerConfig) -> Self {
         Self { config }
     }
 }
 
 impl InstallationStage for ContainerStage {
-    fn name(&self) -> &'static str {
-        "Container Orchestration"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_blue().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
         let images = [
             "alpine:latest",
             "nginx:1.21-alpine",
@@ -42,60 +52,70 @@ impl InstallationStage for ContainerStage {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
 
-            println!(
+            outln!(
                 "{} Pulling {}",
-                LogGenerator::timestamp().dimmed(),
-                image.cyan()
+                LogGenerator::timestamp().timestamp(),
+                image.accent()
             );
 
             if rng.gen_bool(self.config.failure_rate_image_pull) {
-                thread::sleep(Duration::from_millis(rng.gen_range(500..1500)));
-                println!(
+                clock::sleep(Duration::from_millis(rng.gen_range(500..1500)));
+                outln!(
                     "{} Error: Connection timed out while pulling {}",
-                    LogGenerator::timestamp().red(),
+                    LogGenerator::timestamp().error(),
                     image
                 );
-                thread::sleep(Duration::from_millis(1000));
-                println!("{} Retrying in 3s...", LogGenerator::timestamp().yellow());
-                thread::sleep(Duration::from_millis(3000));
-                println!(
+                clock::sleep(Duration::from_millis(1000));
+                outln!("{} Retrying in 3s...", LogGenerator::timestamp().warning());
+                clock::sleep(Duration::from_millis(3000));
+                outln!(
                     "{} Retrying pull for {}",
-                    LogGenerator::timestamp().dimmed(),
-                    image.cyan()
+                    LogGenerator::timestamp().timestamp(),
+                    image.accent()
                 );
             }
 
             let layers = rng.gen_range(3..8);
+            let mut pulls = MultiProgress::new();
             for _i in 0..layers {
                 let layer_id = LogGenerator::hex_addr();
                 let short_id = &layer_id[2..14];
-                let progress = ProgressBar::new(ProgressStyle::Block);
-                progress.animate(
-                    &format!("  {} Pulling fs layer", short_id)
-                        .dimmed()
-                        .to_string(),
+                let size_mb: u64 = rng.gen_range(1..250);
+                let speed_mb: u64 = rng.gen_range(5..40);
+                let curve = match rng.gen_range(0..10) {
+                    0 => ProgressCurve::Stall,
+                    1..=2 => ProgressCurve::Jump,
+                    _ => ProgressCurve::Linear,
+                };
+                let progress = ProgressBar::new(ProgressStyle::Block)
+                    .with_transfer(size_mb << 20, speed_mb << 20)
+                    .with_eta()
+                    .with_curve(curve);
+                pulls.add(
+                    format!("  {} Pulling fs layer", short_id).dim().to_string(),
                     rng.gen_range(self.config.layer_pull_speed_range.clone()),
-                    exit_check,
-                )?;
+                    progress,
+                );
             }
+            pulls.animate(exit_check)?;
 
-            println!(
+            outln!(
                 "{} Digest: sha256:{}",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 LogGenerator::hex_addr()
             );
-            println!(
+            outln!(
                 "{} Status: Downloaded newer image for {}",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 image
             );
-            thread::sleep(Duration::from_millis(300));
+            clock::sleep(Duration::from_millis(300));
         }
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} Initializing Kubernetes cluster...",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
 
         let pods = [
@@ -111,97 +131,97 @@ impl InstallationStage for ContainerStage {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
 
-            println!(
+            outln!(
                 "{} Scaled up replica set {} to 1",
-                LogGenerator::timestamp().dimmed(),
-                format!("{}-rs", pod).cyan()
+                LogGenerator::timestamp().timestamp(),
+                format!("{}-rs", pod).accent()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(100..300)));
+            clock::sleep(Duration::from_millis(rng.gen_range(100..300)));
 
-            println!(
+            outln!(
                 "{} Pod {} Status: {}",
-                LogGenerator::timestamp().dimmed(),
-                pod.yellow(),
-                "Pending".yellow()
+                LogGenerator::timestamp().timestamp(),
+                pod.warning(),
+                "Pending".warning()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(100..300)));
-            println!(
+            clock::sleep(Duration::from_millis(rng.gen_range(100..300)));
+            outln!(
                 "{} Pod {} Status: {}",
-                LogGenerator::timestamp().dimmed(),
-                pod.yellow(),
-                "ContainerCreating".blue()
+                LogGenerator::timestamp().timestamp(),
+                pod.warning(),
+                "ContainerCreating".accent()
             );
 
             if rng.gen_bool(self.config.probability_volume_mount) {
                 let pvc_id = format!("pvc-{}", &LogGenerator::hex_addr()[2..10]);
-                println!(
+                outln!(
                     "{} Mounting volume {} to {}",
-                    LogGenerator::timestamp().dimmed(),
-                    pvc_id.magenta(),
+                    LogGenerator::timestamp().timestamp(),
+                    pvc_id.accent(),
                     pod
                 );
-                thread::sleep(Duration::from_millis(rng.gen_range(200..500)));
+                clock::sleep(Duration::from_millis(rng.gen_range(200..500)));
             }
 
             if rng.gen_bool(self.config.probability_secret_mount) {
-                println!(
+                outln!(
                     "{} Mounting secret {} to {}",
-                    LogGenerator::timestamp().dimmed(),
-                    "vault-token".magenta(),
+                    LogGenerator::timestamp().timestamp(),
+                    "vault-token".accent(),
                     pod
                 );
             }
 
             if rng.gen_bool(self.config.probability_sidecar_injection) {
-                println!(
+                outln!(
                     "{} Injecting sidecar {} to {}",
-                    LogGenerator::timestamp().dimmed(),
-                    "istio-proxy".cyan(),
+                    LogGenerator::timestamp().timestamp(),
+                    "istio-proxy".accent(),
                     pod
                 );
-                thread::sleep(Duration::from_millis(rng.gen_range(100..300)));
+                clock::sleep(Duration::from_millis(rng.gen_range(100..300)));
             }
 
-            thread::sleep(Duration::from_millis(rng.gen_range(200..500)));
+            clock::sleep(Duration::from_millis(rng.gen_range(200..500)));
             if rng.gen_bool(self.config.failure_rate_readiness_probe) {
-                println!(
+                outln!(
                     "{} Warning: Readiness probe failed for {}: Connection refused",
-                    LogGenerator::timestamp().yellow(),
+                    LogGenerator::timestamp().warning(),
                     pod
                 );
-                thread::sleep(Duration::from_millis(800));
+                clock::sleep(Duration::from_millis(800));
             }
-            println!(
+            outln!(
                 "{} Readiness probe passed for {}",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 pod
             );
 
             if rng.gen_bool(self.config.failure_rate_crash_loop) {
-                println!(
+                outln!(
                     "{} Warning: CrashLoopBackOff detected for {}, restarting...",
-                    LogGenerator::timestamp().yellow(),
+                    LogGenerator::timestamp().warning(),
                     pod
                 );
-                thread::sleep(Duration::from_millis(800));
+                clock::sleep(Duration::from_millis(800));
             }
 
-            println!(
+            outln!(
                 "{} Pod {} Status: {}",
-                LogGenerator::timestamp().dimmed(),
-                pod.yellow(),
-                "Running".green()
+                LogGenerator::timestamp().timestamp(),
+                pod.warning(),
+                "Running".success()
             );
-            println!(
+            outln!(
                 "{} Pod {} IP: 10.244.{}.{}",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 pod,
                 rng.gen_range(0..255),
                 rng.gen_range(0..255)
             );
         }
 
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
         Ok(())
     }
 }
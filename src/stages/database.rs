@@ -1,25 +1,37 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::log_generator::LogGenerator;
-use crate::ui::{ProgressBar, ProgressStyle, Spinner};
+use crate::theme::Themed;
+use crate::ui::{ProgressBar, ProgressStyle, Spinner, SpinnerStyle};
 use colored::*;
 use rand::Rng;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
 pub struct DatabaseStage;
 
+impl DatabaseStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "database",
+        name: "Database Server Installation",
+        description: "Database setup",
+        category: Category::Applications,
+        estimated_duration: Duration::from_secs(12),
+        tags: &["modern"],
+    };
+}
+
 impl InstallationStage for DatabaseStage {
-    fn name(&self) -> &'static str {
-        "Database Server Installation"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
-        let mut spinner = Spinner::new();
+        let mut rng = crate::rng::session();
+        let mut spinner = Spinner::with_style(SpinnerStyle::Clock);
 
         let db_type = if rng.gen_bool(0.5) {
             "MySQL"
@@ -28,35 +40,35 @@ impl InstallationStage for DatabaseStage {
         };
         let version = if db_type == "MySQL" { "8.0.28" } else { "14.2" };
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("Installing {} Server {}...", db_type, version).bright_white()
+            LogGenerator::timestamp().timestamp(),
+            format!("Installing {} Server {}...", db_type, version).emphasis()
         );
-        thread::sleep(Duration::from_millis(800));
+        clock::sleep(Duration::from_millis(800));
 
         spinner.animate("Initializing database cluster...", 2000, exit_check)?;
 
         if db_type == "PostgreSQL" {
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 "The files belonging to this database system will be owned by user \"postgres\"."
-                    .dimmed()
+                    .dim()
             );
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "This user must also own the server process.".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "This user must also own the server process.".dim()
             );
-            thread::sleep(Duration::from_millis(500));
+            clock::sleep(Duration::from_millis(500));
         }
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Creating database files...".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "Creating database files...".emphasis()
         );
 
         let files = [
@@ -71,15 +83,15 @@ impl InstallationStage for DatabaseStage {
             if exit_check() {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                format!("  creating {}", file).dimmed()
+                LogGenerator::timestamp().timestamp(),
+                format!("  creating {}", file).dim()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(150..300)));
+            clock::sleep(Duration::from_millis(rng.gen_range(150..300)));
         }
 
-        println!();
+        outln!();
         let progress = ProgressBar::new(ProgressStyle::Equals);
         progress.animate(
             "Initializing system tables:",
@@ -87,17 +99,17 @@ impl InstallationStage for DatabaseStage {
             exit_check,
         )?;
 
-        println!();
+        outln!();
         spinner.animate("Creating template databases...", 1500, exit_check)?;
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Success. You can now start the database server using:".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "Success. You can now start the database server using:".success()
         );
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
+            LogGenerator::timestamp().timestamp(),
             format!(
                 "    {} -D /var/lib/{}/data",
                 if db_type == "PostgreSQL" {
@@ -107,7 +119,7 @@ impl InstallationStage for DatabaseStage {
                 },
                 db_type.to_lowercase()
             )
-            .dimmed()
+            .dim()
         );
 
         Ok(())
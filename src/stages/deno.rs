@@ -1,12 +1,12 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::deno_logs::DenoLogs;
 use crate::log_generator::LogGenerator;
-use crate::ui::{ProgressBar, ProgressStyle};
+use crate::theme::Themed;
+use crate::ui::{Answer, ProgressBar, ProgressStyle, Prompt};
 use colored::*;
-use crossterm::event::{self, Event, KeyCode};
 use rand::Rng;
-use std::io::{self, Write};
-use std::thread;
+use std::io;
 use std::time::Duration;
 
 pub struct DenoStage {
@@ -14,6 +14,15 @@ pub struct DenoStage {
 }
 
 impl DenoStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "deno",
+        name: "Deno Runtime Compilation",
+        description: "Deno runtime compilation",
+        category: Category::Build,
+        estimated_duration: Duration::from_secs(700),
+        tags: &["modern", "long"],
+    };
+
     pub fn new() -> Self {
         Self {
             deno_logs: DenoLogs::load(),
@@ -22,7 +31,7 @@ impl DenoStage {
 
     /// Display logs line by line with realistic delays and progress bars
     fn display_logs(&self, logs: &[String], exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
         for log in logs {
             if exit_check() {
@@ -30,13 +39,13 @@ impl DenoStage {
             }
 
             if log.contains("error:") || log.contains("Error") {
-                println!(
+                outln!("{} {}", LogGenerator::timestamp().timestamp(), log.error());
+            } else if log.contains("warning:") {
+                outln!(
                     "{} {}",
-                    LogGenerator::timestamp().dimmed(),
-                    log.bright_red()
+                    LogGenerator::timestamp().timestamp(),
+                    log.warning()
                 );
-            } else if log.contains("warning:") {
-                println!("{} {}", LogGenerator::timestamp().dimmed(), log.yellow());
             } else if log.contains("Compiling") {
                 let speed_category = rng.gen_range(0..10);
                 let duration = if speed_category < 3 {
@@ -49,142 +58,134 @@ impl DenoStage {
 
                 let progress = ProgressBar::new(ProgressStyle::Block);
                 progress.animate(
-                    &format!("{} {}", LogGenerator::timestamp().dimmed(), log.green()),
+                    &format!(
+                        "{} {}",
+                        LogGenerator::timestamp().timestamp(),
+                        log.success()
+                    ),
                     duration,
                     exit_check,
                 )?;
             } else if log.contains("Downloading") || log.contains("Downloaded") {
-                println!("{} {}", LogGenerator::timestamp().dimmed(), log.cyan());
-                thread::sleep(Duration::from_millis(rng.gen_range(10..40)));
+                outln!("{} {}", LogGenerator::timestamp().timestamp(), log.accent());
+                clock::sleep(Duration::from_millis(rng.gen_range(10..40)));
             } else if log.contains("Finished") {
-                println!(
+                outln!(
                     "{} {}",
-                    LogGenerator::timestamp().dimmed(),
-                    log.bright_green().bold()
+                    LogGenerator::timestamp().timestamp(),
+                    log.success().bold()
                 );
-                thread::sleep(Duration::from_millis(300));
+                clock::sleep(Duration::from_millis(300));
             } else {
-                println!("{} {}", LogGenerator::timestamp().dimmed(), log);
-                thread::sleep(Duration::from_millis(rng.gen_range(20..80)));
+                outln!("{} {}", LogGenerator::timestamp().timestamp(), log);
+                clock::sleep(Duration::from_millis(rng.gen_range(20..80)));
             }
         }
 
         Ok(())
     }
 
-    /// Prompt user to retry or abort
-    fn prompt_retry(&self) -> io::Result<bool> {
-        println!();
-        print!("{}", "Try again or abort? [1-2]: ".bright_yellow().bold());
-        io::stdout().flush()?;
-
-        loop {
-            if let Ok(Event::Key(key_event)) = event::read() {
-                match key_event.code {
-                    KeyCode::Char('1') => {
-                        println!("1");
-                        println!(
-                            "{} {}",
-                            LogGenerator::timestamp().dimmed(),
-                            "Retrying compilation...".bright_cyan()
-                        );
-                        thread::sleep(Duration::from_millis(1000));
-                        return Ok(true);
-                    }
-                    KeyCode::Char('2') => {
-                        println!("2");
-                        println!(
-                            "{} {}",
-                            LogGenerator::timestamp().dimmed(),
-                            "Aborting...".bright_red()
-                        );
-                        thread::sleep(Duration::from_millis(500));
-                        return Ok(false);
-                    }
-                    _ => {
-                        continue;
-                    }
-                }
-            }
+    /// Ask whether to retry the build, giving up on it when told to abort
+    fn prompt_retry(&self, exit_check: &dyn Fn() -> bool) -> io::Result<bool> {
+        outln!();
+        let answer =
+            Prompt::choice("Try again or abort?", &["Try again", "Abort"], 0).ask(exit_check)?;
+
+        if answer == Answer::Choice(0) {
+            outln!(
+                "{} {}",
+                LogGenerator::timestamp().timestamp(),
+                "Retrying compilation...".accent()
+            );
+            clock::sleep(Duration::from_millis(1000));
+            Ok(true)
+        } else {
+            outln!(
+                "{} {}",
+                LogGenerator::timestamp().timestamp(),
+                "Aborting...".error()
+            );
+            clock::sleep(Duration::from_millis(500));
+            Ok(false)
         }
     }
 }
 
 impl InstallationStage for DenoStage {
-    fn name(&self) -> &'static str {
-        "Deno Runtime Compilation"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
         let should_fail = rng.gen_bool(0.3);
 
         if should_fail {
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Building Deno from source...".bright_white()
+                LogGenerator::timestamp().timestamp(),
+                "Building Deno from source...".emphasis()
             );
-            println!();
+            outln!();
 
             self.display_logs(self.deno_logs.error_logs(), exit_check)?;
 
-            println!();
-            println!(
+            outln!();
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Build failed! The installation encountered errors.".bright_red()
+                LogGenerator::timestamp().timestamp(),
+                "Build failed! The installation encountered errors.".error()
             );
 
-            let retry = self.prompt_retry()?;
+            let retry = self.prompt_retry(exit_check)?;
 
             if retry {
-                println!();
-                println!(
+                outln!();
+                outln!(
                     "{} {}",
-                    LogGenerator::timestamp().dimmed(),
-                    "Rebuilding Deno from source...".bright_white()
+                    LogGenerator::timestamp().timestamp(),
+                    "Rebuilding Deno from source...".emphasis()
                 );
-                println!();
+                outln!();
 
                 self.display_logs(self.deno_logs.success_logs(), exit_check)?;
 
-                println!();
-                println!(
+                outln!();
+                outln!(
                     "{} {}",
-                    LogGenerator::timestamp().dimmed(),
-                    "Build completed successfully!".bright_green().bold()
+                    LogGenerator::timestamp().timestamp(),
+                    "Build completed successfully!".success().bold()
                 );
             } else {
-                println!(
+                outln!(
                     "{} {}",
-                    LogGenerator::timestamp().dimmed(),
-                    "Skipping Deno installation...".dimmed()
+                    LogGenerator::timestamp().timestamp(),
+                    "Skipping Deno installation...".dim()
                 );
             }
         } else {
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Building Deno from source...".bright_white()
+                LogGenerator::timestamp().timestamp(),
+                "Building Deno from source...".emphasis()
             );
-            println!();
+            outln!();
 
             self.display_logs(self.deno_logs.success_logs(), exit_check)?;
 
-            println!();
-            println!(
+            outln!();
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Build completed successfully!".bright_green().bold()
+                LogGenerator::timestamp().timestamp(),
+                "Build completed successfully!".success().bold()
             );
         }
 
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
         Ok(())
     }
 }
@@ -0,0 +1,258 @@
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
+use crate::config::DosConfig;
+use crate::output;
+use crate::theme::Themed;
+use crate::ui::{self, ProgressBar, ProgressStyle, Prompt};
+use colored::*;
+use crossterm::{cursor, queue, style::Print};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// Column the dialogs are drawn from
+const DIALOG_COLUMN: u16 = 4;
+
+/// Most bad disks Setup rejects before the right one finally reads
+const MAX_BAD_DISKS: u32 = 3;
+
+/// Programs the stage pretends to install: (name, directory, files on each disk)
+const PRODUCTS: &[(&str, &str, &[&str])] = &[
+    (
+        "Microsoft Word 5.5",
+        "WORD",
+        &[
+            "WORD.EXE",
+            "SPELL-AM.LEX",
+            "THES-AM.SYN",
+            "WORD.HLP",
+            "PRINTERS.DAT",
+        ],
+    ),
+    (
+        "Lotus 1-2-3 Release 2.4",
+        "123R24",
+        &[
+            "123.EXE",
+            "123.CMP",
+            "WYSIWYG.ADN",
+            "INSTALL.LBR",
+            "123.DYN",
+        ],
+    ),
+    (
+        "Borland Turbo Pascal 7.0",
+        "TP",
+        &["TPX.EXE", "TURBO.TPL", "TPC.EXE", "BGI.ZIP", "TVISION.ZIP"],
+    ),
+    (
+        "Wolfenstein 3-D",
+        "WOLF3D",
+        &[
+            "WOLF3D.EXE",
+            "VSWAP.WL6",
+            "AUDIOT.WL6",
+            "GAMEMAPS.WL6",
+            "VGAGRAPH.WL6",
+        ],
+    ),
+    (
+        "Norton Commander 5.0",
+        "NC",
+        &["NC.EXE", "NCMAIN.EXE", "NCVIEW.EXE", "NC.HLP", "NCEDIT.EXE"],
+    ),
+];
+
+pub struct DosStage {
+    config: DosConfig,
+}
+
+impl DosStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "dos",
+        name: "DOS Software Setup",
+        description: "DOS floppy installer with disk swaps",
+        category: Category::Applications,
+        estimated_duration: Duration::from_secs(60),
+        tags: &["retro", "dos"],
+    };
+
+    pub fn new(config: DosConfig) -> Self {
+        Self { config }
+    }
+
+    /// Draw a text-mode dialog box with a drop shadow
+    fn dialog(title: &str, lines: &[String]) -> io::Result<()> {
+        let inner = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .chain([title.chars().count() + 2])
+            .max()
+            .unwrap_or(0)
+            + 2;
+
+        let title = format!(" {} ", title);
+        let fill = inner - title.chars().count();
+        let mut rows = vec![format!(
+            "╔{}{}{}╗",
+            "═".repeat(fill / 2),
+            title,
+            "═".repeat(fill - fill / 2)
+        )];
+        rows.extend(
+            lines
+                .iter()
+                .map(|line| format!("║ {:<width$} ║", line, width = inner - 2)),
+        );
+        rows.push(format!("╚{}╝", "═".repeat(inner)));
+
+        let mut out = output::stdout();
+        for (index, row) in rows.iter().enumerate() {
+            if ui::is_plain() {
+                queue!(out, Print(" ".repeat(DIALOG_COLUMN as usize)))?;
+            } else {
+                queue!(out, cursor::MoveToColumn(DIALOG_COLUMN))?;
+            }
+            // Shadow down the right-hand side, below the title bar; logs get none
+            let shadow = if index == 0 || ui::is_plain() {
+                "".normal()
+            } else {
+                " ".shadow()
+            };
+            queue!(out, Print(row.as_str().panel()), Print(shadow), Print("\n"))?;
+        }
+        out.flush()
+    }
+
+    /// Wait for ENTER, carrying on by itself once the prompt times out
+    fn wait_for_enter(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
+        Prompt::enter("    Waiting for ENTER...")
+            .timeout(Duration::from_millis(self.config.prompt_timeout))
+            .ask(exit_check)?;
+        Ok(())
+    }
+
+    fn swap_disk(&self, disk: u32, disks: u32, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
+        let mut rng = crate::rng::session();
+        outln!();
+        Self::dialog(
+            "Setup",
+            &[
+                format!("Please insert Disk {} of {} into drive A:", disk, disks),
+                "and press ENTER".to_string(),
+            ],
+        )?;
+        self.wait_for_enter(exit_check)?;
+
+        // The wrong disk, or the right one with a bad sector, goes back in the drive
+        for _ in 0..MAX_BAD_DISKS {
+            if !rng.gen_bool(self.config.bad_disk_chance) {
+                break;
+            }
+            outln!();
+            let problem = if rng.gen_bool(0.5) {
+                format!("The disk in drive A: is not Disk {}.", disk)
+            } else {
+                "General failure reading drive A:".to_string()
+            };
+            Self::dialog(
+                "Error",
+                &[
+                    problem,
+                    format!("Please insert Disk {} and press ENTER", disk),
+                ],
+            )?;
+            self.wait_for_enter(exit_check)?;
+        }
+        Ok(())
+    }
+}
+
+impl InstallationStage for DosStage {
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
+    }
+
+    fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
+
+        let mut rng = crate::rng::session();
+        let (product, directory, files) = *PRODUCTS.choose(&mut rng).unwrap();
+        let disks = rng.gen_range(self.config.disk_count_range.clone());
+
+        outln!("{}", "A:\\>INSTALL".emphasis());
+        clock::sleep(Duration::from_millis(600));
+        Self::dialog(
+            &format!("{} Setup", product),
+            &[
+                format!("This program installs {} on your", product),
+                "hard disk. Setup needs about 4 MB of free space.".to_string(),
+                String::new(),
+                format!("Install to: C:\\{}", directory),
+                String::new(),
+                "Press ENTER to continue, or CTRL+C to quit.".to_string(),
+            ],
+        )?;
+        self.wait_for_enter(exit_check)?;
+
+        let gauge = ProgressBar::new(ProgressStyle::Dos);
+        for disk in 1..=disks {
+            if disk > 1 {
+                self.swap_disk(disk, disks, exit_check)?;
+            }
+
+            let count = rng.gen_range(1..=3);
+            for file in files.choose_multiple(&mut rng, count) {
+                if exit_check() {
+                    return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
+                }
+                gauge.animate(
+                    &format!("    Copying A:\\{:<13}", file),
+                    rng.gen_range(self.config.copy_speed_range.clone()),
+                    exit_check,
+                )?;
+            }
+        }
+
+        outln!();
+        Self::dialog(
+            "Setup Complete",
+            &[
+                format!("{} is now installed in C:\\{}.", product, directory),
+                "Remove the disk from drive A: and type".to_string(),
+                format!("CD \\{} to get started.", directory),
+            ],
+        )?;
+        outln!();
+        outln!("{}", "C:\\>".emphasis());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::thread;
+
+    #[test]
+    fn test_every_disk_bad_still_finishes() {
+        let config = DosConfig {
+            disk_count_range: 2..3,
+            copy_speed_range: 1..2,
+            prompt_timeout: 0,
+            bad_disk_chance: 1.0,
+        };
+
+        let (done, finished) = mpsc::channel();
+        thread::spawn(move || {
+            clock::set_thread_speed(f64::INFINITY);
+            output::mute_thread();
+            let result = DosStage::new(config).run(&|| false);
+            let _ = done.send(result.is_ok());
+        });
+        assert_eq!(finished.recv_timeout(Duration::from_secs(10)), Ok(true));
+    }
+}
@@ -1,48 +1,61 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::log_generator::LogGenerator;
 use crate::messages::DRIVERS;
+use crate::output;
+use crate::theme::Themed;
 use colored::*;
 use rand::Rng;
-use std::io::{self, Write};
-use std::thread;
+use std::io;
 use std::time::Duration;
 
 pub struct DriversStage;
 
+impl DriversStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "drivers",
+        name: "Hardware Driver Installation",
+        description: "Driver installation",
+        category: Category::System,
+        estimated_duration: Duration::from_secs(7),
+        tags: &["retro", "hardware"],
+    };
+}
+
 impl InstallationStage for DriversStage {
-    fn name(&self) -> &'static str {
-        "Hardware Driver Installation"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
         for (device, driver) in DRIVERS {
             if exit_check() {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
 
-            print!(
+            out!(
                 "{} Detecting: {} ",
-                LogGenerator::timestamp().dimmed(),
-                device.bright_cyan()
+                LogGenerator::timestamp().timestamp(),
+                device.accent()
             );
-            io::stdout().flush()?;
-            thread::sleep(Duration::from_millis(rng.gen_range(400..900)));
+            output::flush()?;
+            clock::sleep(Duration::from_millis(rng.gen_range(400..900)));
 
-            println!("{}", "[FOUND]".bright_green());
-            println!(
+            outln!("{}", "[FOUND]".success());
+            outln!(
                 "{}   └─ Loading driver: {}",
-                LogGenerator::timestamp().dimmed(),
-                driver.dimmed()
+                LogGenerator::timestamp().timestamp(),
+                driver.dim()
             );
-            thread::sleep(Duration::from_millis(300));
+            clock::sleep(Duration::from_millis(300));
         }
 
-        println!();
+        outln!();
         Ok(())
     }
 }
@@ -0,0 +1,45 @@
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
+use crate::theme::Themed;
+use crate::ui::Pager;
+use colored::*;
+use std::io;
+use std::time::Duration;
+
+/// The agreement nobody reads
+const LICENSE: &str = include_str!("../../data/eula.txt");
+
+pub struct EulaStage;
+
+impl EulaStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "eula",
+        name: "License Agreement",
+        description: "End-user license agreement you have to scroll through",
+        category: Category::Applications,
+        estimated_duration: Duration::from_secs(20),
+        tags: &["interactive", "opt-in"],
+    };
+}
+
+impl InstallationStage for EulaStage {
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
+    }
+
+    fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
+        outln!(
+            "{}",
+            "Please read the following license agreement carefully.".emphasis()
+        );
+        outln!();
+
+        Pager::new("End-User License Agreement", LICENSE).accept("I Agree", exit_check)?;
+
+        clock::sleep(Duration::from_millis(400));
+        outln!("{}", "License accepted. Thank you.".success());
+        Ok(())
+    }
+}
@@ -1,77 +1,89 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::log_generator::LogGenerator;
+use crate::theme::Themed;
 use crate::ui::{ProgressBar, ProgressStyle};
 use colored::*;
 use rand::Rng;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
 pub struct FilesystemStage;
 
+impl FilesystemStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "filesystem",
+        name: "Filesystem Operations",
+        description: "Filesystem setup",
+        category: Category::System,
+        estimated_duration: Duration::from_secs(13),
+        tags: &["linux"],
+    };
+}
+
 impl InstallationStage for FilesystemStage {
-    fn name(&self) -> &'static str {
-        "Filesystem Operations"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Creating ext4 filesystem on /dev/sda2...".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "Creating ext4 filesystem on /dev/sda2...".emphasis()
         );
-        thread::sleep(Duration::from_millis(600));
+        clock::sleep(Duration::from_millis(600));
 
         let blocks = rng.gen_range(50000000..100000000);
         let inodes = blocks / 4;
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("mke2fs 1.45.5 (07-Jan-2020)").dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "mke2fs 1.45.5 (07-Jan-2020)".dim()
         );
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
+            LogGenerator::timestamp().timestamp(),
             format!(
                 "Creating filesystem with {} 4k blocks and {} inodes",
                 blocks, inodes
             )
-            .dimmed()
+            .dim()
         );
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
         if exit_check() {
             return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
         }
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Filesystem UUID: 8f3e1a2b-4c5d-6e7f-8a9b-0c1d2e3f4a5b".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Filesystem UUID: 8f3e1a2b-4c5d-6e7f-8a9b-0c1d2e3f4a5b".dim()
         );
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("Superblock backups stored on blocks:").dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Superblock backups stored on blocks:".dim()
         );
 
         let backup_blocks = [32768, 98304, 163840, 229376, 294912];
         for block in &backup_blocks {
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                format!("        {}", block).dimmed()
+                LogGenerator::timestamp().timestamp(),
+                format!("        {}", block).dim()
             );
-            thread::sleep(Duration::from_millis(100));
+            clock::sleep(Duration::from_millis(100));
         }
 
-        println!();
+        outln!();
         let progress = ProgressBar::new(ProgressStyle::Equals);
         progress.animate(
             "Allocating group tables:",
@@ -86,79 +98,79 @@ impl InstallationStage for FilesystemStage {
             exit_check,
         )?;
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Creating journal (32768 blocks): ".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Creating journal (32768 blocks): ".dim()
         );
-        thread::sleep(Duration::from_millis(rng.gen_range(800..1200)));
-        println!(
+        clock::sleep(Duration::from_millis(rng.gen_range(800..1200)));
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "done".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "done".success()
         );
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Writing superblocks and filesystem accounting information: ".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Writing superblocks and filesystem accounting information: ".dim()
         );
-        thread::sleep(Duration::from_millis(rng.gen_range(600..1000)));
-        println!(
+        clock::sleep(Duration::from_millis(rng.gen_range(600..1000)));
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "done".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "done".success()
         );
 
-        println!();
+        outln!();
 
         if rng.gen_bool(0.4) {
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Running filesystem check...".bright_white()
+                LogGenerator::timestamp().timestamp(),
+                "Running filesystem check...".emphasis()
             );
-            thread::sleep(Duration::from_millis(500));
-            println!(
+            clock::sleep(Duration::from_millis(500));
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "e2fsck 1.45.5 (07-Jan-2020)".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "e2fsck 1.45.5 (07-Jan-2020)".dim()
             );
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Pass 1: Checking inodes, blocks, and sizes".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "Pass 1: Checking inodes, blocks, and sizes".dim()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(800..1500)));
-            println!(
+            clock::sleep(Duration::from_millis(rng.gen_range(800..1500)));
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Pass 2: Checking directory structure".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "Pass 2: Checking directory structure".dim()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(600..1000)));
-            println!(
+            clock::sleep(Duration::from_millis(rng.gen_range(600..1000)));
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Pass 3: Checking directory connectivity".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "Pass 3: Checking directory connectivity".dim()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(400..800)));
-            println!(
+            clock::sleep(Duration::from_millis(rng.gen_range(400..800)));
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Pass 4: Checking reference counts".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "Pass 4: Checking reference counts".dim()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(400..700)));
-            println!(
+            clock::sleep(Duration::from_millis(rng.gen_range(400..700)));
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Pass 5: Checking group summary information".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "Pass 5: Checking group summary information".dim()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(300..600)));
-            println!(
+            clock::sleep(Duration::from_millis(rng.gen_range(300..600)));
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 "/dev/sda2: 11/2048000 files (0.0% non-contiguous), 200000/8192000 blocks"
-                    .bright_green()
+                    .success()
             );
         }
 
@@ -1,36 +1,48 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::log_generator::LogGenerator;
+use crate::theme::Themed;
 use crate::ui::{ProgressBar, ProgressStyle};
 use colored::*;
 use rand::Rng;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
 pub struct InitramfsStage;
 
+impl InitramfsStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "initramfs",
+        name: "Initial RAM Filesystem",
+        description: "Initramfs generation",
+        category: Category::Boot,
+        estimated_duration: Duration::from_secs(10),
+        tags: &["linux"],
+    };
+}
+
 impl InstallationStage for InitramfsStage {
-    fn name(&self) -> &'static str {
-        "Initial RAM Filesystem"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
         let kernel_version = "5.4.0-42-generic";
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
+            LogGenerator::timestamp().timestamp(),
             format!(
                 "update-initramfs: Generating /boot/initrd.img-{}",
                 kernel_version
             )
-            .bright_white()
+            .emphasis()
         );
-        thread::sleep(Duration::from_millis(800));
+        clock::sleep(Duration::from_millis(800));
 
         let modules = [
             "kernel/drivers/ata/libata.ko",
@@ -49,15 +61,15 @@ impl InstallationStage for InitramfsStage {
         }
 
         for module in &modules {
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                format!("Adding module: {}", module).dimmed()
+                LogGenerator::timestamp().timestamp(),
+                format!("Adding module: {}", module).dim()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(100..250)));
+            clock::sleep(Duration::from_millis(rng.gen_range(100..250)));
         }
 
-        println!();
+        outln!();
         let progress = ProgressBar::new(ProgressStyle::Block);
         progress.animate(
             "Copying binaries and libraries:",
@@ -65,19 +77,19 @@ impl InstallationStage for InitramfsStage {
             exit_check,
         )?;
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Creating initramfs image...".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "Creating initramfs image...".emphasis()
         );
-        thread::sleep(Duration::from_millis(rng.gen_range(1000..2000)));
+        clock::sleep(Duration::from_millis(rng.gen_range(1000..2000)));
 
         let size_mb = rng.gen_range(25..45);
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("Image size: {}MB", size_mb).bright_green()
+            LogGenerator::timestamp().timestamp(),
+            format!("Image size: {}MB", size_mb).success()
         );
 
         Ok(())
@@ -1,11 +1,12 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::kernel_logs::KernelLogs;
 use crate::log_generator::LogGenerator;
+use crate::theme::Themed;
 use crate::ui::{ProgressBar, ProgressStyle};
 use colored::*;
 use rand::Rng;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
 pub struct KernelStage {
@@ -13,6 +14,15 @@ pub struct KernelStage {
 }
 
 impl KernelStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "kernel",
+        name: "Linux Kernel Compilation",
+        description: "Linux kernel compilation",
+        category: Category::Build,
+        estimated_duration: Duration::from_secs(900),
+        tags: &["linux", "long"],
+    };
+
     pub fn new() -> Self {
         Self {
             kernel_logs: KernelLogs::load(),
@@ -21,7 +31,7 @@ impl KernelStage {
 
     /// Display all kernel logs with progress bars for initialization steps
     fn display_logs(&self, logs: &[String], exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
         for log in logs {
             if exit_check() {
@@ -46,11 +56,7 @@ impl KernelStage {
 
                 let progress = ProgressBar::new(ProgressStyle::Block);
                 progress.animate(
-                    &format!(
-                        "{} {}",
-                        LogGenerator::timestamp().dimmed(),
-                        log.bright_cyan()
-                    ),
+                    &format!("{} {}", LogGenerator::timestamp().timestamp(), log.accent()),
                     duration,
                     exit_check,
                 )?;
@@ -64,8 +70,8 @@ impl KernelStage {
                     rng.gen_range(100..400)
                 };
 
-                println!("{} {}", LogGenerator::timestamp().dimmed(), log.dimmed());
-                thread::sleep(Duration::from_millis(delay));
+                outln!("{} {}", LogGenerator::timestamp().timestamp(), log.dim());
+                clock::sleep(Duration::from_millis(delay));
             }
         }
 
@@ -74,31 +80,31 @@ impl KernelStage {
 }
 
 impl InstallationStage for KernelStage {
-    fn name(&self) -> &'static str {
-        "Linux Kernel Compilation"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Building Linux kernel from source...".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "Building Linux kernel from source...".emphasis()
         );
-        println!();
+        outln!();
 
         self.display_logs(self.kernel_logs.all_logs(), exit_check)?;
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Kernel build completed successfully!".bright_green().bold()
+            LogGenerator::timestamp().timestamp(),
+            "Kernel build completed successfully!".success().bold()
         );
 
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
         Ok(())
     }
 }
@@ -1,31 +1,43 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::log_generator::LogGenerator;
+use crate::theme::Themed;
 use crate::ui::{ProgressBar, ProgressStyle};
 use colored::*;
 use rand::Rng;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
 pub struct LocaleStage;
 
+impl LocaleStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "locale",
+        name: "Localization Configuration",
+        description: "Locale configuration",
+        category: Category::System,
+        estimated_duration: Duration::from_secs(11),
+        tags: &["linux"],
+    };
+}
+
 impl InstallationStage for LocaleStage {
-    fn name(&self) -> &'static str {
-        "Localization Configuration"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Generating locales...".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "Generating locales...".emphasis()
         );
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
 
         let locales = [
             "en_US.UTF-8",
@@ -41,20 +53,20 @@ impl InstallationStage for LocaleStage {
             if exit_check() {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                format!("Generating locale {}... ", locale).dimmed()
+                LogGenerator::timestamp().timestamp(),
+                format!("Generating locale {}... ", locale).dim()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(300..700)));
-            println!(
+            clock::sleep(Duration::from_millis(rng.gen_range(300..700)));
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "done".bright_green()
+                LogGenerator::timestamp().timestamp(),
+                "done".success()
             );
         }
 
-        println!();
+        outln!();
         let progress = ProgressBar::new(ProgressStyle::Hash);
         progress.animate(
             "Building locale archive:",
@@ -62,11 +74,11 @@ impl InstallationStage for LocaleStage {
             exit_check,
         )?;
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Configuring timezone...".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "Configuring timezone...".emphasis()
         );
         let timezones = [
             "America/New_York",
@@ -76,12 +88,12 @@ impl InstallationStage for LocaleStage {
             "Asia/Tokyo",
         ];
         let timezone = timezones[rng.gen_range(0..timezones.len())];
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("  Timezone set to: {}", timezone).dimmed()
+            LogGenerator::timestamp().timestamp(),
+            format!("  Timezone set to: {}", timezone).dim()
         );
-        thread::sleep(Duration::from_millis(600));
+        clock::sleep(Duration::from_millis(600));
 
         Ok(())
     }
@@ -1,42 +1,54 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::log_generator::LogGenerator;
-use crate::ui::Spinner;
+use crate::theme::Themed;
+use crate::ui::{Spinner, SpinnerStyle};
 use colored::*;
 use rand::Rng;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
 pub struct NetworkStage;
 
+impl NetworkStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "network",
+        name: "Network Configuration",
+        description: "Network configuration",
+        category: Category::System,
+        estimated_duration: Duration::from_secs(8),
+        tags: &["network"],
+    };
+}
+
 impl InstallationStage for NetworkStage {
-    fn name(&self) -> &'static str {
-        "Network Configuration"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
-        let mut spinner = Spinner::new();
+        let mut rng = crate::rng::session();
+        let mut spinner = Spinner::with_style(SpinnerStyle::Arrows);
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Configuring network interfaces...".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "Configuring network interfaces...".emphasis()
         );
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
 
         let interfaces = ["eth0", "enp0s3", "wlan0"];
         let interface = interfaces[rng.gen_range(0..interfaces.len())];
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("  Interface: {}", interface).dimmed()
+            LogGenerator::timestamp().timestamp(),
+            format!("  Interface: {}", interface).dim()
         );
-        thread::sleep(Duration::from_millis(300));
+        clock::sleep(Duration::from_millis(300));
 
         if exit_check() {
             return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
@@ -55,45 +67,45 @@ impl InstallationStage for NetworkStage {
         );
         let gateway = format!("192.168.{}.1", rng.gen_range(0..255));
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("  IP Address: {}", ip).bright_green()
+            LogGenerator::timestamp().timestamp(),
+            format!("  IP Address: {}", ip).success()
         );
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("  Netmask: 255.255.255.0").dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "  Netmask: 255.255.255.0".dim()
         );
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("  Gateway: {}", gateway).dimmed()
+            LogGenerator::timestamp().timestamp(),
+            format!("  Gateway: {}", gateway).dim()
         );
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("  DNS: 8.8.8.8, 8.8.4.4").dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "  DNS: 8.8.8.8, 8.8.4.4".dim()
         );
-        thread::sleep(Duration::from_millis(600));
+        clock::sleep(Duration::from_millis(600));
 
-        println!();
+        outln!();
         spinner.animate("Configuring DNS resolution...", 1200, exit_check)?;
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Updating /etc/resolv.conf".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Updating /etc/resolv.conf".dim()
         );
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
         if rng.gen_bool(0.3) {
-            println!();
+            outln!();
             spinner.animate("Testing network connectivity...", 1500, exit_check)?;
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Network is reachable".bright_green()
+                LogGenerator::timestamp().timestamp(),
+                "Network is reachable".success()
             );
         }
 
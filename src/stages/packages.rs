@@ -1,71 +1,110 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::log_generator::LogGenerator;
 use crate::messages::PACKAGES;
-use crate::ui::{ProgressBar, ProgressStyle, Spinner};
+use crate::theme::Themed;
+use crate::ui::{ProgressBar, ProgressCurve, ProgressStyle, Prompt, Spinner, SpinnerStyle};
 use colored::*;
 use rand::Rng;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
 pub struct PackagesStage;
 
+impl PackagesStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "packages",
+        name: "Package Installation",
+        description: "Package installation",
+        category: Category::Applications,
+        estimated_duration: Duration::from_secs(26),
+        tags: &["linux"],
+    };
+}
+
 impl InstallationStage for PackagesStage {
-    fn name(&self) -> &'static str {
-        "Package Installation"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut spinner = Spinner::new();
+        let mut spinner = Spinner::with_style(SpinnerStyle::Braille);
         spinner.animate("Reading package lists...", 1200, exit_check)?;
         spinner.animate("Building dependency tree...", 1500, exit_check)?;
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
+
+        outln!(
+            "{} upgraded, {} newly installed, 0 to remove and {} not upgraded.",
+            rng.gen_range(0..40),
+            PACKAGES.len(),
+            rng.gen_range(0..12)
+        );
+        outln!(
+            "After this operation, {} MB of additional disk space will be used.",
+            rng.gen_range(80..900)
+        );
+        if !Prompt::yes_no("Do you want to continue?", true)
+            .brief()
+            .ask(exit_check)?
+            .is_yes()
+        {
+            outln!("{}", "Abort.".error());
+            return Ok(());
+        }
 
         for package in PACKAGES {
             if exit_check() {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
 
-            println!(
+            outln!(
                 "{} Unpacking {}...",
-                LogGenerator::timestamp().dimmed(),
-                package.bright_white()
+                LogGenerator::timestamp().timestamp(),
+                package.emphasis()
             );
 
             let size_kb: u32 = rng.gen_range(512..8192);
             let speed_kb: u32 = rng.gen_range(64..512);
 
-            let progress = ProgressBar::new(ProgressStyle::Hash);
+            let curve = if rng.gen_bool(0.2) {
+                ProgressCurve::Jump
+            } else {
+                ProgressCurve::Linear
+            };
+            let progress = ProgressBar::new(ProgressStyle::Hash)
+                .with_transfer(size_kb as u64 * 1024, speed_kb as u64 * 1024)
+                .with_eta()
+                .with_curve(curve);
             progress.animate(
-                &format!("  ({:.1}MB @ {}KB/s)", size_kb as f32 / 1024.0, speed_kb),
+                &format!("  ({:.1}MB)", size_kb as f32 / 1024.0),
                 rng.gen_range(1000..2500),
                 exit_check,
             )?;
 
             if rng.gen_bool(0.4) {
-                println!(
+                outln!(
                     "{} {}",
-                    LogGenerator::timestamp().dimmed(),
-                    format!("Setting up {}...", package).dimmed()
+                    LogGenerator::timestamp().timestamp(),
+                    format!("Setting up {}...", package).dim()
                 );
-                thread::sleep(Duration::from_millis(300));
+                clock::sleep(Duration::from_millis(300));
             }
         }
 
-        println!(
+        outln!(
             "\n{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Processing triggers for shared libraries...".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Processing triggers for shared libraries...".dim()
         );
-        thread::sleep(Duration::from_millis(800));
-        println!(
+        clock::sleep(Duration::from_millis(800));
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "ldconfig: /usr/lib/x86_64-linux-gnu/libcrypto.so.1.1".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "ldconfig: /usr/lib/x86_64-linux-gnu/libcrypto.so.1.1".dim()
         );
 
         Ok(())
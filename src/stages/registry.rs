@@ -0,0 +1,305 @@
+use super::script::{ScriptStage, StageScript};
+use super::*;
+use crate::config::SimulationConfig;
+use std::fmt;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// Broad grouping of stages, used to organize help text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Firmware,
+    Boot,
+    System,
+    Build,
+    Applications,
+    Infrastructure,
+    Custom,
+}
+
+impl Category {
+    const ALL: [Category; 7] = [
+        Category::Firmware,
+        Category::Boot,
+        Category::System,
+        Category::Build,
+        Category::Applications,
+        Category::Infrastructure,
+        Category::Custom,
+    ];
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label = match self {
+            Category::Firmware => "Firmware",
+            Category::Boot => "Boot",
+            Category::System => "System",
+            Category::Build => "Build",
+            Category::Applications => "Applications",
+            Category::Infrastructure => "Infrastructure",
+            Category::Custom => "Custom",
+        };
+        f.write_str(label)
+    }
+}
+
+/// How a stage describes itself to the registry
+#[derive(Debug)]
+pub struct StageInfo {
+    /// Name used on the command line
+    pub id: &'static str,
+    /// Title printed when the stage starts
+    pub name: &'static str,
+    /// One-line summary shown in help text
+    pub description: &'static str,
+    pub category: Category,
+    /// Rough wall-clock time of one run at normal speed
+    pub estimated_duration: Duration,
+    pub tags: &'static [&'static str],
+}
+
+/// How a registered stage is instantiated
+enum Builder {
+    Native(fn(&SimulationConfig) -> Box<dyn InstallationStage>),
+    Script(&'static StageScript),
+}
+
+/// A registered stage and how to build it from the simulation config
+pub struct StageEntry {
+    pub info: &'static StageInfo,
+    build: Builder,
+}
+
+impl StageEntry {
+    pub fn build(&self, config: &SimulationConfig) -> Box<dyn InstallationStage> {
+        match self.build {
+            Builder::Native(build) => build(config),
+            Builder::Script(script) => Box::new(ScriptStage::new(script, self.info)),
+        }
+    }
+}
+
+/// Every built-in stage, in canonical installation order
+static BUILTIN: &[StageEntry] = &[
+    StageEntry {
+        info: &BiosStage::INFO,
+        build: Builder::Native(|config| Box::new(BiosStage::new(config.bios.clone()))),
+    },
+    StageEntry {
+        info: &BootStage::INFO,
+        build: Builder::Native(|config| Box::new(BootStage::new(config.boot.clone()))),
+    },
+    StageEntry {
+        info: &BootloaderStage::INFO,
+        build: Builder::Native(|config| Box::new(BootloaderStage::new(config.bootloader.clone()))),
+    },
+    StageEntry {
+        info: &FilesystemStage::INFO,
+        build: Builder::Native(|_| Box::new(FilesystemStage)),
+    },
+    StageEntry {
+        info: &SystemStage::INFO,
+        build: Builder::Native(|_| Box::new(SystemStage)),
+    },
+    StageEntry {
+        info: &NetworkStage::INFO,
+        build: Builder::Native(|_| Box::new(NetworkStage)),
+    },
+    StageEntry {
+        info: &DriversStage::INFO,
+        build: Builder::Native(|_| Box::new(DriversStage)),
+    },
+    StageEntry {
+        info: &InitramfsStage::INFO,
+        build: Builder::Native(|_| Box::new(InitramfsStage)),
+    },
+    StageEntry {
+        info: &PackagesStage::INFO,
+        build: Builder::Native(|_| Box::new(PackagesStage)),
+    },
+    StageEntry {
+        info: &KernelStage::INFO,
+        build: Builder::Native(|_| Box::new(KernelStage::new())),
+    },
+    StageEntry {
+        info: &CompilationStage::INFO,
+        build: Builder::Native(|_| Box::new(CompilationStage::new())),
+    },
+    StageEntry {
+        info: &DenoStage::INFO,
+        build: Builder::Native(|_| Box::new(DenoStage::new())),
+    },
+    StageEntry {
+        info: &DatabaseStage::INFO,
+        build: Builder::Native(|_| Box::new(DatabaseStage)),
+    },
+    StageEntry {
+        info: &XorgStage::INFO,
+        build: Builder::Native(|_| Box::new(XorgStage)),
+    },
+    StageEntry {
+        info: &ServicesStage::INFO,
+        build: Builder::Native(|_| Box::new(ServicesStage)),
+    },
+    StageEntry {
+        info: &WindowsStage::INFO,
+        build: Builder::Native(|config| Box::new(WindowsStage::new(config.windows.clone()))),
+    },
+    StageEntry {
+        info: &DosStage::INFO,
+        build: Builder::Native(|config| Box::new(DosStage::new(config.dos.clone()))),
+    },
+    StageEntry {
+        info: &RetroSoftwareStage::INFO,
+        build: Builder::Native(|_| Box::new(RetroSoftwareStage)),
+    },
+    StageEntry {
+        info: &LocaleStage::INFO,
+        build: Builder::Native(|_| Box::new(LocaleStage)),
+    },
+    StageEntry {
+        info: &ContainerStage::INFO,
+        build: Builder::Native(|config| Box::new(ContainerStage::new(config.container.clone()))),
+    },
+    StageEntry {
+        info: &AiStage::INFO,
+        build: Builder::Native(|config| Box::new(AiStage::new(config.ai.clone()))),
+    },
+    StageEntry {
+        info: &CloudStage::INFO,
+        build: Builder::Native(|config| Box::new(CloudStage::new(config.cloud.clone()))),
+    },
+];
+
+/// Built-in stages that only run when asked for by name, never as part of the default set
+static OPT_IN: &[StageEntry] = &[StageEntry {
+    info: &EulaStage::INFO,
+    build: Builder::Native(|_| Box::new(EulaStage)),
+}];
+
+/// Stages loaded from script files at runtime; they run after the built-in stages
+static SCRIPTED: RwLock<Vec<&'static StageEntry>> = RwLock::new(Vec::new());
+
+fn entries() -> Vec<&'static StageEntry> {
+    BUILTIN
+        .iter()
+        .chain(OPT_IN)
+        .chain(SCRIPTED.read().unwrap().iter().copied())
+        .collect()
+}
+
+/// Add a scripted stage to the registry; it lives for the rest of the program
+pub fn register_script(script: StageScript) -> Result<Stage, String> {
+    if Stage::from_id(&script.id).is_some() {
+        return Err(format!("a stage named '{}' already exists", script.id));
+    }
+
+    let script: &'static StageScript = Box::leak(Box::new(script));
+    let entry: &'static StageEntry = Box::leak(Box::new(StageEntry {
+        info: Box::leak(Box::new(script.describe())),
+        build: Builder::Script(script),
+    }));
+    SCRIPTED.write().unwrap().push(entry);
+    Ok(Stage(entry.info.id))
+}
+
+/// Handle to a registered stage, identified by its command-line id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Stage(&'static str);
+
+impl Stage {
+    /// Returns all stages that run by default, in installation order
+    pub fn all() -> Vec<Stage> {
+        Self::registered()
+            .into_iter()
+            .filter(|stage| !stage.is_opt_in())
+            .collect()
+    }
+
+    /// Returns every registered stage in installation order, opt-in stages included
+    pub fn registered() -> Vec<Stage> {
+        entries()
+            .into_iter()
+            .map(|entry| Stage(entry.info.id))
+            .collect()
+    }
+
+    /// Whether the stage is left out unless named on the command line
+    pub fn is_opt_in(&self) -> bool {
+        OPT_IN.iter().any(|entry| entry.info.id == self.0)
+    }
+
+    /// Look up a registered stage by id (case-insensitive)
+    pub fn from_id(id: &str) -> Option<Stage> {
+        entries()
+            .into_iter()
+            .find(|entry| entry.info.id.eq_ignore_ascii_case(id))
+            .map(|entry| Stage(entry.info.id))
+    }
+
+    fn entry(&self) -> &'static StageEntry {
+        entries()
+            .into_iter()
+            .find(|entry| entry.info.id == self.0)
+            .expect("stage handles are only created from registry entries")
+    }
+
+    pub fn build(&self, config: &SimulationConfig) -> Box<dyn InstallationStage> {
+        self.entry().build(config)
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// Help text listing every stage grouped by category, derived from the registry
+pub fn stage_overview() -> String {
+    let mut overview = String::from("Stages:\n");
+
+    for category in Category::ALL {
+        let members: Vec<_> = entries()
+            .into_iter()
+            .filter(|entry| entry.info.category == category)
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+
+        overview.push_str(&format!("\n  {}:\n", category));
+        for entry in members {
+            let info = entry.info;
+            let secs = info.estimated_duration.as_secs();
+            let estimate = if secs >= 60 {
+                format!("~{}m", secs / 60)
+            } else {
+                format!("~{}s", secs)
+            };
+            overview.push_str(&format!(
+                "    {:<12} {:<30} {:>5}  [{}]\n",
+                info.id,
+                info.description,
+                estimate,
+                info.tags.join(", ")
+            ));
+        }
+    }
+
+    overview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opt_in_stages_are_left_out_of_all() {
+        let eula = Stage::from_id("eula").unwrap();
+        assert!(eula.is_opt_in());
+        assert!(!Stage::all().contains(&eula));
+        assert!(Stage::registered().contains(&eula));
+    }
+}
@@ -1,69 +1,81 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::log_generator::LogGenerator;
 use crate::messages::RETRO_SOFTWARE;
+use crate::theme::Themed;
 use crate::ui::{ProgressBar, ProgressStyle};
 use colored::*;
 use rand::Rng;
 use std::io;
-use std::thread;
 use std::time::Duration;
 
 pub struct RetroSoftwareStage;
 
+impl RetroSoftwareStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "retro",
+        name: "Retro Software Installation",
+        description: "Retro software installation",
+        category: Category::Applications,
+        estimated_duration: Duration::from_secs(22),
+        tags: &["retro"],
+    };
+}
+
 impl InstallationStage for RetroSoftwareStage {
-    fn name(&self) -> &'static str {
-        "Retro Software Installation"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
         for (name, version, size_kb) in RETRO_SOFTWARE {
             if exit_check() {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
 
-            println!(
+            outln!(
                 "\n{} {} {} {}",
-                LogGenerator::timestamp().dimmed(),
-                "Installing".bright_white(),
-                name.bright_cyan().bold(),
-                format!("v{}", version).dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "Installing".emphasis(),
+                name.accent().bold(),
+                format!("v{}", version).dim()
             );
 
             if rng.gen_bool(0.2) {
-                println!(
+                outln!(
                     "{} {}",
-                    LogGenerator::timestamp().dimmed(),
-                    "Checking for previous installation...".dimmed()
+                    LogGenerator::timestamp().timestamp(),
+                    "Checking for previous installation...".dim()
                 );
-                thread::sleep(Duration::from_millis(500));
+                clock::sleep(Duration::from_millis(500));
             }
 
-            let progress = ProgressBar::new(ProgressStyle::Block);
+            let progress = ProgressBar::new(ProgressStyle::Gradient);
             progress.animate(
                 &format!("  Extracting files ({:.1}MB):", *size_kb as f32 / 1024.0),
                 rng.gen_range(2000..4000),
                 exit_check,
             )?;
 
-            println!(
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                "  Creating shortcuts...".dimmed()
+                LogGenerator::timestamp().timestamp(),
+                "  Creating shortcuts...".dim()
             );
-            thread::sleep(Duration::from_millis(400));
+            clock::sleep(Duration::from_millis(400));
 
             if rng.gen_bool(0.3) {
-                println!(
+                outln!(
                     "{} {}",
-                    LogGenerator::timestamp().dimmed(),
-                    "  Registering file associations...".dimmed()
+                    LogGenerator::timestamp().timestamp(),
+                    "  Registering file associations...".dim()
                 );
-                thread::sleep(Duration::from_millis(300));
+                clock::sleep(Duration::from_millis(300));
             }
         }
 
@@ -0,0 +1,523 @@
+use super::registry::register_script;
+use super::{Category, InstallationStage, Stage, StageInfo};
+use crate::clock;
+use crate::log_generator::LogGenerator;
+use crate::rng::SessionRng;
+use crate::theme::Themed;
+use crate::ui::{ProgressBar, ProgressCurve, ProgressStyle, Spinner, SpinnerStyle};
+use colored::*;
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use serde::Deserialize;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// A user-defined stage loaded from a TOML script file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StageScript {
+    /// Name used on the command line
+    pub id: String,
+    /// Title printed when the stage starts
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(rename = "step")]
+    pub steps: Vec<Step>,
+}
+
+/// A single instruction in a stage script
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Step {
+    /// Print a timestamped log line
+    Log {
+        text: String,
+        #[serde(default)]
+        level: Level,
+        #[serde(default = "default_log_delay")]
+        delay_ms: Delay,
+    },
+    /// Show a spinner next to a message
+    Spinner {
+        message: String,
+        duration_ms: Delay,
+        /// Frames to use instead of the theme's spinner
+        #[serde(default)]
+        style: Option<SpinnerStyle>,
+    },
+    /// Fill a progress bar next to a message
+    Progress {
+        message: String,
+        duration_ms: Delay,
+        #[serde(default = "default_progress_style")]
+        style: ProgressStyle,
+        #[serde(default)]
+        curve: ProgressCurve,
+    },
+    /// Run one of several step lists, picked at random by weight
+    Choice {
+        #[serde(rename = "option")]
+        options: Vec<ChoiceOption>,
+    },
+    /// Run the nested steps, failing with `chance` and retrying up to `retries` times;
+    /// when every attempt fails the stage gives up on them and carries on
+    Failure {
+        message: String,
+        chance: f64,
+        #[serde(default = "default_retries")]
+        retries: u32,
+        #[serde(default = "default_retry_delay")]
+        retry_delay_ms: Delay,
+        #[serde(default, rename = "step")]
+        steps: Vec<Step>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChoiceOption {
+    #[serde(default = "default_weight")]
+    weight: u32,
+    #[serde(rename = "step")]
+    steps: Vec<Step>,
+}
+
+/// How a log line is colored
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Level {
+    #[default]
+    Info,
+    Ok,
+    Warn,
+    Error,
+    Dim,
+}
+
+/// A delay in milliseconds: either fixed (`500`) or a random `[min, max]` range
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(untagged)]
+pub enum Delay {
+    Fixed(u64),
+    Range([u64; 2]),
+}
+
+impl Delay {
+    fn sample(&self, rng: &mut SessionRng) -> u64 {
+        match *self {
+            Delay::Fixed(ms) => ms,
+            Delay::Range([min, max]) => rng.gen_range(min..max),
+        }
+    }
+
+    fn average(&self) -> u64 {
+        match *self {
+            Delay::Fixed(ms) => ms,
+            Delay::Range([min, max]) => (min + max) / 2,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match *self {
+            Delay::Range([min, max]) if min >= max => Err(format!(
+                "delay range must be [min, max] with min < max, got [{}, {}]",
+                min, max
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn default_log_delay() -> Delay {
+    Delay::Range([50, 200])
+}
+
+fn default_progress_style() -> ProgressStyle {
+    ProgressStyle::Hash
+}
+
+fn default_retries() -> u32 {
+    3
+}
+
+fn default_retry_delay() -> Delay {
+    Delay::Fixed(1500)
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+impl StageScript {
+    /// Load and validate a stage script
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        Self::parse(&content).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let script: Self = toml::from_str(content).map_err(|e| e.to_string())?;
+        script.validate()?;
+        Ok(script)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let valid_id = !self.id.is_empty()
+            && self
+                .id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if !valid_id {
+            return Err(format!(
+                "id '{}' must only use lowercase letters, digits, '-' and '_'",
+                self.id
+            ));
+        }
+        if self.steps.is_empty() {
+            return Err("a stage needs at least one [[step]]".to_string());
+        }
+        validate_steps(&self.steps, "step")
+    }
+
+    /// Registry metadata for this script; the script must outlive the program
+    pub fn describe(&'static self) -> StageInfo {
+        let description = if self.description.is_empty() {
+            &self.name
+        } else {
+            &self.description
+        };
+        let tags: Vec<&'static str> = self.tags.iter().map(String::as_str).collect();
+
+        StageInfo {
+            id: &self.id,
+            name: &self.name,
+            description,
+            category: Category::Custom,
+            estimated_duration: Duration::from_millis(estimate(&self.steps)),
+            tags: Box::leak(tags.into_boxed_slice()),
+        }
+    }
+}
+
+fn validate_steps(steps: &[Step], path: &str) -> Result<(), String> {
+    for (index, step) in steps.iter().enumerate() {
+        let at = format!("{} {}", path, index + 1);
+        let context = |e: String| format!("{}: {}", at, e);
+        match step {
+            Step::Log { delay_ms, .. } => delay_ms.validate().map_err(context)?,
+            Step::Spinner { duration_ms, .. } | Step::Progress { duration_ms, .. } => {
+                duration_ms.validate().map_err(context)?
+            }
+            Step::Choice { options } => {
+                if options.is_empty() {
+                    return Err(context(
+                        "a choice needs at least one [[option]]".to_string(),
+                    ));
+                }
+                if options.iter().all(|option| option.weight == 0) {
+                    return Err(context(
+                        "at least one option needs a weight above 0".to_string(),
+                    ));
+                }
+                for (option_index, option) in options.iter().enumerate() {
+                    validate_steps(
+                        &option.steps,
+                        &format!("{}, option {}, step", at, option_index + 1),
+                    )?;
+                }
+            }
+            Step::Failure {
+                chance,
+                retry_delay_ms,
+                steps,
+                ..
+            } => {
+                if !(0.0..=1.0).contains(chance) {
+                    return Err(context(format!(
+                        "chance must be between 0.0 and 1.0, got {}",
+                        chance
+                    )));
+                }
+                retry_delay_ms.validate().map_err(context)?;
+                validate_steps(steps, &format!("{}, step", at))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rough running time of a list of steps in milliseconds
+fn estimate(steps: &[Step]) -> u64 {
+    steps
+        .iter()
+        .map(|step| match step {
+            Step::Log { delay_ms, .. } => delay_ms.average(),
+            Step::Spinner { duration_ms, .. } | Step::Progress { duration_ms, .. } => {
+                duration_ms.average()
+            }
+            Step::Choice { options } => options
+                .iter()
+                .map(|option| estimate(&option.steps))
+                .max()
+                .unwrap_or(0),
+            Step::Failure { steps, .. } => estimate(steps),
+        })
+        .sum()
+}
+
+/// Replace `{hex}` and `{version}` placeholders with fresh generated values
+fn expand(text: &str) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("{hex}") {
+            expanded.push_str(&LogGenerator::hex_addr());
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("{version}") {
+            expanded.push_str(&LogGenerator::version());
+            rest = after;
+        } else {
+            expanded.push('{');
+            rest = &rest[1..];
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Load a stage script and add it to the stage registry
+pub fn load_script(path: &Path) -> io::Result<Stage> {
+    let script = StageScript::load(path)?;
+    register_script(script).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), e),
+        )
+    })
+}
+
+/// Load every `*.toml` stage script in a directory, in file name order; a missing directory
+/// is fine. A broken script doesn't stop the others from loading: each file gets its own result
+pub fn load_script_dir(dir: &Path) -> Vec<io::Result<Stage>> {
+    if !dir.is_dir() {
+        return Vec::new();
+    }
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            return vec![Err(io::Error::new(
+                e.kind(),
+                format!("{}: {}", dir.display(), e),
+            ))]
+        }
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    paths.iter().map(|path| load_script(path)).collect()
+}
+
+/// Runs a `StageScript` through the same primitives as the built-in stages
+pub struct ScriptStage {
+    script: &'static StageScript,
+    info: &'static StageInfo,
+}
+
+impl ScriptStage {
+    pub fn new(script: &'static StageScript, info: &'static StageInfo) -> Self {
+        Self { script, info }
+    }
+
+    fn run_steps(
+        &self,
+        steps: &[Step],
+        rng: &mut SessionRng,
+        exit_check: &dyn Fn() -> bool,
+    ) -> io::Result<()> {
+        for step in steps {
+            if exit_check() {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
+            }
+
+            match step {
+                Step::Log {
+                    text,
+                    level,
+                    delay_ms,
+                } => {
+                    let text = expand(text);
+                    let text = match level {
+                        Level::Info => text.normal(),
+                        Level::Ok => text.success(),
+                        Level::Warn => text.warning(),
+                        Level::Error => text.error(),
+                        Level::Dim => text.dim(),
+                    };
+                    outln!("{} {}", LogGenerator::timestamp().timestamp(), text);
+                    clock::sleep(Duration::from_millis(delay_ms.sample(rng)));
+                }
+                Step::Spinner {
+                    message,
+                    duration_ms,
+                    style,
+                } => {
+                    let mut spinner = style.map_or_else(Spinner::new, Spinner::with_style);
+                    spinner.animate(&expand(message), duration_ms.sample(rng), exit_check)?;
+                }
+                Step::Progress {
+                    message,
+                    duration_ms,
+                    style,
+                    curve,
+                } => {
+                    let progress = ProgressBar::new(*style).with_curve(*curve);
+                    progress.animate(&expand(message), duration_ms.sample(rng), exit_check)?;
+                }
+                Step::Choice { options } => {
+                    let weights = options.iter().map(|option| option.weight);
+                    if let Ok(distribution) = WeightedIndex::new(weights) {
+                        let option = &options[distribution.sample(rng)];
+                        self.run_steps(&option.steps, rng, exit_check)?;
+                    }
+                }
+                Step::Failure {
+                    message,
+                    chance,
+                    retries,
+                    retry_delay_ms,
+                    steps,
+                } => {
+                    for attempt in 1..=retries + 1 {
+                        self.run_steps(steps, rng, exit_check)?;
+                        if !rng.gen_bool(*chance) {
+                            break;
+                        }
+
+                        outln!(
+                            "{} {}",
+                            LogGenerator::timestamp().timestamp(),
+                            expand(message).error()
+                        );
+                        if attempt > *retries {
+                            outln!(
+                                "{} {}",
+                                LogGenerator::timestamp().timestamp(),
+                                format!("Giving up after {} attempts, continuing anyway", attempt)
+                                    .warning()
+                            );
+                            break;
+                        }
+                        outln!(
+                            "{} {}",
+                            LogGenerator::timestamp().timestamp(),
+                            format!("Retrying... (attempt {}/{})", attempt + 1, retries + 1)
+                                .warning()
+                        );
+                        clock::sleep(Duration::from_millis(retry_delay_ms.sample(rng)));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl InstallationStage for ScriptStage {
+    fn info(&self) -> &'static StageInfo {
+        self.info
+    }
+
+    fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
+
+        let mut rng = crate::rng::session();
+        self.run_steps(&self.script.steps, &mut rng, exit_check)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACME: &str = r#"
+        id = "acme"
+        name = "Installing AcmeCorp Agent"
+
+        [[step]]
+        type = "log"
+        text = "Resolving agent.acme.example..."
+
+        [[step]]
+        type = "progress"
+        message = "Downloading acme-agent {version}"
+        duration_ms = [800, 1600]
+        style = "block"
+
+        [[step]]
+        type = "choice"
+        [[step.option]]
+        weight = 3
+        [[step.option.step]]
+        type = "log"
+        text = "Using primary mirror"
+        [[step.option]]
+        [[step.option.step]]
+        type = "spinner"
+        message = "Falling back to secondary mirror"
+        duration_ms = 1200
+
+        [[step]]
+        type = "failure"
+        message = "E: Handshake with license server failed"
+        chance = 0.5
+        [[step.step]]
+        type = "spinner"
+        message = "Activating license"
+        duration_ms = 900
+    "#;
+
+    #[test]
+    fn test_parse_valid_script() {
+        let script = StageScript::parse(ACME).unwrap();
+        assert_eq!(script.id, "acme");
+        assert_eq!(script.steps.len(), 4);
+    }
+
+    #[test]
+    fn test_invalid_chance_reports_step() {
+        let script = ACME.replace("chance = 0.5", "chance = 2.0");
+        let err = StageScript::parse(&script).unwrap_err();
+        assert!(err.starts_with("step 4:"), "{}", err);
+    }
+
+    #[test]
+    fn test_unknown_step_type_rejected() {
+        let script = ACME.replace("type = \"log\"", "type = \"beep\"");
+        assert!(StageScript::parse(&script).is_err());
+    }
+
+    #[test]
+    fn test_expand_placeholders() {
+        let expanded = expand("id={hex} {literal}");
+        assert!(expanded.starts_with("id=0x"));
+        assert!(expanded.ends_with(" {literal}"));
+    }
+}
@@ -1,23 +1,37 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::log_generator::LogGenerator;
+use crate::output;
+use crate::theme::Themed;
+use crate::ui;
 use colored::*;
 use rand::Rng;
-use std::io::{self, Write};
-use std::thread;
+use std::io;
 use std::time::Duration;
 
 pub struct ServicesStage;
 
+impl ServicesStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "services",
+        name: "System Services Configuration",
+        description: "Services configuration",
+        category: Category::System,
+        estimated_duration: Duration::from_secs(11),
+        tags: &["linux"],
+    };
+}
+
 impl InstallationStage for ServicesStage {
-    fn name(&self) -> &'static str {
-        "System Services Configuration"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
         let services = [
             ("NetworkManager.service", "Network Manager"),
@@ -35,44 +49,44 @@ impl InstallationStage for ServicesStage {
             ("apache2.service", "The Apache HTTP Server"),
         ];
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Starting system services...".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "Starting system services...".emphasis()
         );
-        println!();
+        outln!();
 
         for (_service, description) in &services {
             if exit_check() {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
 
-            print!(
+            out!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                format!("[ ** ] Starting {}...", description).bright_cyan()
+                LogGenerator::timestamp().timestamp(),
+                format!("[ ** ] Starting {}...", description).accent()
             );
-            io::stdout().flush()?;
-            thread::sleep(Duration::from_millis(rng.gen_range(300..800)));
-            print!("\r");
-            println!(
+            output::flush()?;
+            clock::sleep(Duration::from_millis(rng.gen_range(300..800)));
+            ui::rewind_line()?;
+            outln!(
                 "{} {}",
-                LogGenerator::timestamp().dimmed(),
-                format!("[ OK ] Started {}.", description).bright_green()
+                LogGenerator::timestamp().timestamp(),
+                format!("[ OK ] Started {}.", description).success()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(100..300)));
+            clock::sleep(Duration::from_millis(rng.gen_range(100..300)));
         }
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
+            LogGenerator::timestamp().timestamp(),
             format!(
                 "Loaded {} services, {} active",
                 services.len(),
                 services.len()
             )
-            .dimmed()
+            .dim()
         );
 
         Ok(())
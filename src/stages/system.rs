@@ -1,38 +1,55 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::log_generator::LogGenerator;
 use crate::messages::SYSTEM_COMPONENTS;
+use crate::output;
+use crate::theme::Themed;
 use crate::ui::{ProgressBar, ProgressStyle};
 use colored::*;
 use rand::Rng;
-use std::io::{self, Write};
-use std::thread;
+use std::io;
 use std::time::Duration;
 
 pub struct SystemStage;
 
+impl SystemStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "system",
+        name: "System Component Installation",
+        description: "System installation",
+        category: Category::System,
+        estimated_duration: Duration::from_secs(9),
+        tags: &["linux"],
+    };
+}
+
 impl InstallationStage for SystemStage {
-    fn name(&self) -> &'static str {
-        "System Component Installation"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
 
         for component in SYSTEM_COMPONENTS {
             if exit_check() {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
 
-            print!("{}   - {} ", LogGenerator::timestamp().dimmed(), component);
-            io::stdout().flush()?;
-            thread::sleep(Duration::from_millis(rng.gen_range(300..800)));
-            println!("{}", "[OK]".bright_green());
+            out!(
+                "{}   - {} ",
+                LogGenerator::timestamp().timestamp(),
+                component
+            );
+            output::flush()?;
+            clock::sleep(Duration::from_millis(rng.gen_range(300..800)));
+            outln!("{}", "[OK]".success());
         }
 
-        println!();
+        outln!();
         let progress = ProgressBar::new(ProgressStyle::Equals);
         progress.animate(
             "Building module dependencies:",
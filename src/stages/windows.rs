@@ -0,0 +1,398 @@
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
+use crate::config::{WindowsConfig, WindowsEdition};
+use crate::output;
+use crate::theme::Themed;
+use crate::ui::{self, ProgressBar, ProgressStyle, Spinner};
+use colored::*;
+use crossterm::{
+    cursor, execute,
+    terminal::{self, ClearType},
+};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::io;
+use std::time::Duration;
+
+/// Columns of the text-mode setup screen
+const SCREEN_WIDTH: usize = 70;
+
+/// Everything that differs between one Windows setup and the next
+struct Profile {
+    /// Banner at the top of the text-mode screen
+    title: &'static str,
+    /// Explanation shown above the copy gauge
+    copying: &'static [&'static str],
+    /// Prefix of the per-file status line
+    status: &'static str,
+    files: &'static [&'static str],
+    /// Steps listed down the side of the billboard phase
+    steps: &'static [&'static str],
+    billboards: &'static [&'static str],
+    /// Wording of the remaining-time estimate, or `None` to show a percentage instead
+    estimate: Option<&'static str>,
+    /// The estimate the billboard phase opens with, in minutes
+    minutes: u32,
+    /// Shown while the machine "reboots"
+    restart: &'static str,
+    /// Shown after the final reboot
+    first_boot: &'static [&'static str],
+}
+
+const WINDOWS_95: Profile = Profile {
+    title: "Windows 95 Setup",
+    copying: &[
+        "Setup is copying files...",
+        "Please wait while Setup copies Windows 95 files to your computer.",
+    ],
+    status: "Copying file:",
+    files: &[
+        "C:\\WINDOWS\\SYSTEM\\KRNL386.EXE",
+        "C:\\WINDOWS\\SYSTEM\\USER.EXE",
+        "C:\\WINDOWS\\SYSTEM\\GDI.EXE",
+        "C:\\WINDOWS\\SYSTEM\\VMM32.VXD",
+        "C:\\WINDOWS\\COMMAND\\SCANDISK.EXE",
+        "C:\\WINDOWS\\EXPLORER.EXE",
+        "C:\\WINDOWS\\MEDIA\\THE MICROSOFT SOUND.WAV",
+        "C:\\WINDOWS\\WIN.COM",
+        "C:\\WINDOWS\\SYSTEM\\MSDOS.SYS",
+        "C:\\WINDOWS\\SYSTEM\\SHELL32.DLL",
+    ],
+    steps: &[
+        "Collecting information",
+        "Copying Windows 95 files",
+        "Restarting the computer",
+        "Finishing Setup",
+    ],
+    billboards: &[
+        "Welcome to Windows 95! Start here: click the Start button.",
+        "Windows 95 runs your favorite MS-DOS-based games.",
+        "Plug and Play finds and configures your hardware for you.",
+        "Long filenames: 'Quarterly Report' instead of QTRRPT~1.DOC.",
+        "The Microsoft Network puts the world at your fingertips.",
+    ],
+    estimate: Some("Estimated time remaining:"),
+    minutes: 45,
+    restart: "Setup will now restart your computer.",
+    first_boot: &[
+        "Getting ready to run Windows 95 for the first time...",
+        "Setting up Control Panel",
+        "Setting up Programs on the Start menu",
+        "Setting up Windows Help",
+        "Setting up time zone",
+    ],
+};
+
+const WINDOWS_XP: Profile = Profile {
+    title: "Windows XP Professional Setup",
+    copying: &[
+        "Please wait while Setup copies files to the Windows installation",
+        "folders. This might take several minutes to complete.",
+        "",
+        "Setup is copying files...",
+    ],
+    status: "Copying:",
+    files: &[
+        "ntoskrnl.exe",
+        "hal.dll",
+        "ntdll.dll",
+        "kernel32.dll",
+        "explorer.exe",
+        "msgina.dll",
+        "bliss.bmp",
+        "pinball.exe",
+        "spider.exe",
+        "clippy.acs",
+        "mshtml.dll",
+        "winlogon.exe",
+    ],
+    steps: &[
+        "Collecting information",
+        "Dynamic Update",
+        "Preparing installation",
+        "Installing Windows",
+        "Finalizing installation",
+    ],
+    billboards: &[
+        "Windows XP: the most reliable Windows ever.",
+        "Make your computer your own with Windows XP themes.",
+        "Stay connected with Windows Messenger.",
+        "Fast User Switching: share your PC without closing programs.",
+        "Windows Movie Maker turns your home videos into movies.",
+    ],
+    estimate: Some("Setup will complete in approximately:"),
+    minutes: 39,
+    restart: "Setup will restart your computer in {} seconds",
+    first_boot: &[
+        "Welcome to Microsoft Windows",
+        "Thank you for choosing Microsoft Windows XP.",
+        "Let's spend a few minutes setting up your computer.",
+    ],
+};
+
+const WINDOWS_10: Profile = Profile {
+    title: "Windows Setup",
+    copying: &["Installing Windows", "", "Copying Windows files"],
+    status: "Getting files ready for installation:",
+    files: &[
+        "install.wim",
+        "boot.wim",
+        "Microsoft-Windows-Client-Features-Package.cab",
+        "Microsoft-Windows-Cortana-Package.cab",
+        "Microsoft-OneCore-ApplicationModel.cab",
+        "Windows-Defender-Core.cab",
+        "CandyCrushSaga.appx",
+    ],
+    steps: &[
+        "Copying Windows files",
+        "Getting files ready for installation",
+        "Installing features",
+        "Installing updates",
+        "Finishing up",
+    ],
+    billboards: &[
+        "Hi.",
+        "We're setting things up for you",
+        "This might take several minutes",
+        "Leave everything to us",
+        "Don't turn off your PC",
+        "Getting things ready",
+        "Working on it...",
+    ],
+    estimate: None,
+    minutes: 0,
+    restart: "Restarting",
+    first_boot: &[
+        "Let's start with region. Is this right?",
+        "Please wait",
+        "Just a moment...",
+    ],
+};
+
+pub struct WindowsStage {
+    config: WindowsConfig,
+}
+
+impl WindowsStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "windows",
+        name: "Windows Setup",
+        description: "Windows 95 / XP / 10 setup",
+        category: Category::System,
+        estimated_duration: Duration::from_secs(40),
+        tags: &["retro", "windows"],
+    };
+
+    pub fn new(config: WindowsConfig) -> Self {
+        Self { config }
+    }
+
+    /// One row of the blue text-mode screen
+    fn screen_line(text: &str) -> String {
+        format!("  {:<width$}", text, width = SCREEN_WIDTH - 2)
+            .screen()
+            .to_string()
+    }
+
+    fn copy_files(&self, profile: &Profile, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
+        let mut rng = crate::rng::session();
+
+        outln!("{}", Self::screen_line(profile.title).bold());
+        outln!("{}", Self::screen_line(&"═".repeat(profile.title.len())));
+        outln!("{}", Self::screen_line(""));
+        for line in profile.copying {
+            outln!("{}", Self::screen_line(line));
+        }
+        outln!("{}", Self::screen_line(""));
+
+        let gauge = ProgressBar::new(ProgressStyle::Block).with_width(40);
+        let steps = rng.gen_range(self.config.file_count_range.clone());
+        for step in 0..=steps {
+            if exit_check() {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
+            }
+
+            let progress = step as f32 / steps as f32;
+            let file = profile.files.choose(&mut rng).unwrap();
+            if ui::is_plain() {
+                ui::redraw_line(&gauge.render(progress), step as u64, steps as u64)?;
+            } else {
+                // Gauge on one row, the file being copied on the status bar beneath it
+                if step > 0 {
+                    execute!(output::stdout(), cursor::MoveUp(2))?;
+                }
+                let bar = gauge.render(progress);
+                let padding = SCREEN_WIDTH.saturating_sub(ui::visible_width(&bar) + 2);
+                out!("\r{}{}{}", "  ".screen(), bar, " ".repeat(padding).screen());
+                outln!();
+                let status = format!(" {} {}", profile.status, file);
+                out!(
+                    "\r{}",
+                    format!("{:<width$}", status, width = SCREEN_WIDTH)
+                        .emphasis()
+                        .reversed()
+                );
+                execute!(output::stdout(), terminal::Clear(ClearType::UntilNewLine))?;
+                outln!();
+                output::flush()?;
+            }
+            clock::sleep(Duration::from_millis(
+                rng.gen_range(self.config.file_delay_range.clone()),
+            ));
+        }
+        Ok(())
+    }
+
+    fn restart(&self, profile: &Profile, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
+        outln!();
+        if profile.restart.contains("{}") {
+            // XP counts down, but never gets anywhere near zero on its own
+            let mut rng = crate::rng::session();
+            let steps = 15;
+            for remaining in (0..=steps).rev() {
+                if exit_check() {
+                    return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
+                }
+                let line = profile.restart.replace("{}", &remaining.to_string());
+                ui::redraw_line(&line.warning().to_string(), steps - remaining, steps)?;
+                clock::sleep(Duration::from_millis(self.config.countdown_tick));
+                if remaining < 12 && rng.gen_bool(0.2) {
+                    break;
+                }
+            }
+            if !ui::is_plain() {
+                outln!();
+            }
+        } else {
+            outln!("{}", profile.restart.warning());
+        }
+
+        clock::sleep(Duration::from_millis(self.config.reboot_delay));
+        outln!("{}", "Restarting...".dim());
+        clock::sleep(Duration::from_millis(self.config.reboot_delay));
+        outln!("{}", "Starting Windows...".emphasis());
+        outln!();
+        Ok(())
+    }
+
+    fn billboards(&self, profile: &Profile, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
+        let mut rng = crate::rng::session();
+        let mut minutes = profile.minutes as i32;
+        let slides = profile.billboards.len();
+        let gauge = ProgressBar::new(ProgressStyle::Block).with_width(30);
+
+        for (index, billboard) in profile.billboards.iter().enumerate() {
+            if exit_check() {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
+            }
+
+            let step = index * profile.steps.len() / slides;
+            outln!(
+                "  {} {}",
+                "●".accent(),
+                profile.steps[step].emphasis().bold()
+            );
+            outln!(
+                "  {}",
+                format!("┌{}┐", "─".repeat(SCREEN_WIDTH - 4)).header()
+            );
+            outln!(
+                "  {}{}{}",
+                "│".header(),
+                format!(" {:<width$}", billboard, width = SCREEN_WIDTH - 5).title(),
+                "│".header()
+            );
+            outln!(
+                "  {}",
+                format!("└{}┘", "─".repeat(SCREEN_WIDTH - 4)).header()
+            );
+
+            let progress = (index + 1) as f32 / (slides + 1) as f32;
+            match profile.estimate {
+                Some(estimate) => {
+                    outln!(
+                        "  {} {} {}",
+                        estimate.dim(),
+                        format!("{} minutes", minutes.max(1)).emphasis(),
+                        gauge.render(progress)
+                    );
+                    // The estimate wanders; it never lands on what actually happens
+                    minutes += match rng.gen_range(0..10) {
+                        0..=1 => rng.gen_range(1..6),
+                        2 => 0,
+                        _ => -rng.gen_range(2..10),
+                    };
+                }
+                None => {
+                    outln!(
+                        "  {}",
+                        format!("{}% complete", (progress * 100.0) as u32).dim()
+                    );
+                }
+            }
+            outln!();
+
+            let mut spinner = Spinner::new();
+            spinner.animate(
+                "Please wait...",
+                rng.gen_range(self.config.billboard_delay_range.clone()),
+                exit_check,
+            )?;
+        }
+
+        if let Some(estimate) = profile.estimate {
+            outln!(
+                "  {} {}",
+                estimate.dim(),
+                format!("{} minutes", minutes.max(1) + 12).warning()
+            );
+            outln!("  {}", "Setup is finishing anyway.".dim());
+        }
+        Ok(())
+    }
+}
+
+impl InstallationStage for WindowsStage {
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
+    }
+
+    fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
+
+        let edition = self.config.edition.unwrap_or_else(|| {
+            *[
+                WindowsEdition::Windows95,
+                WindowsEdition::Xp,
+                WindowsEdition::Windows10,
+            ]
+            .choose(&mut crate::rng::session())
+            .unwrap()
+        });
+        let profile = match edition {
+            WindowsEdition::Windows95 => &WINDOWS_95,
+            WindowsEdition::Xp => &WINDOWS_XP,
+            WindowsEdition::Windows10 => &WINDOWS_10,
+        };
+
+        self.copy_files(profile, exit_check)?;
+        self.restart(profile, exit_check)?;
+        self.billboards(profile, exit_check)?;
+        self.restart(profile, exit_check)?;
+
+        for line in profile.first_boot {
+            if exit_check() {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
+            }
+            outln!("  {}", line.emphasis());
+            clock::sleep(Duration::from_millis(self.config.reboot_delay / 2));
+        }
+        outln!();
+        outln!(
+            "{}",
+            "Windows setup completed successfully.".success().bold()
+        );
+        Ok(())
+    }
+}
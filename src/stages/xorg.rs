@@ -1,27 +1,39 @@
-use super::InstallationStage;
+use super::{Category, InstallationStage, StageInfo};
+use crate::clock;
 use crate::log_generator::LogGenerator;
+use crate::output;
+use crate::rng::SessionRng;
+use crate::theme::Themed;
 use crate::ui::Spinner;
 use colored::*;
 use rand::Rng;
-use std::io::{self, Write};
-use std::thread;
+use std::io;
 use std::time::Duration;
 
 pub struct XorgStage;
 
 impl XorgStage {
+    pub const INFO: StageInfo = StageInfo {
+        id: "xorg",
+        name: "X Window System Setup",
+        description: "X.org configuration",
+        category: Category::System,
+        estimated_duration: Duration::from_secs(33),
+        tags: &["linux", "retro"],
+    };
+
     fn load_xorg_modules(
         &self,
-        rng: &mut rand::rngs::ThreadRng,
+        rng: &mut SessionRng,
         exit_check: &dyn Fn() -> bool,
     ) -> io::Result<()> {
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Loading X server modules...".bright_cyan()
+            LogGenerator::timestamp().timestamp(),
+            "Loading X server modules...".accent()
         );
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
         let modules = [
             ("fb", "Framebuffer support"),
@@ -38,14 +50,14 @@ impl XorgStage {
             if exit_check() {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
-            print!(
+            out!(
                 "{}   [*] {} ",
-                LogGenerator::timestamp().dimmed(),
-                module.bright_white()
+                LogGenerator::timestamp().timestamp(),
+                module.emphasis()
             );
-            io::stdout().flush()?;
-            thread::sleep(Duration::from_millis(rng.gen_range(120..280)));
-            println!("{}", format!("({})", desc).dimmed());
+            output::flush()?;
+            clock::sleep(Duration::from_millis(rng.gen_range(120..280)));
+            outln!("{}", format!("({})", desc).dim());
         }
 
         Ok(())
@@ -53,65 +65,65 @@ impl XorgStage {
 
     fn initialize_glx(
         &self,
-        rng: &mut rand::rngs::ThreadRng,
+        rng: &mut SessionRng,
         _exit_check: &dyn Fn() -> bool,
     ) -> io::Result<()> {
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Initializing GLX (OpenGL Extension)...".bright_cyan()
+            LogGenerator::timestamp().timestamp(),
+            "Initializing GLX (OpenGL Extension)...".accent()
         );
-        thread::sleep(Duration::from_millis(rng.gen_range(400..700)));
+        clock::sleep(Duration::from_millis(rng.gen_range(400..700)));
 
         let gl_versions = ["4.6", "4.5", "4.3"];
         let gl_version = gl_versions[rng.gen_range(0..gl_versions.len())];
 
-        println!(
+        outln!(
             "{}   ├─ GLX version: {}",
-            LogGenerator::timestamp().dimmed(),
-            "1.4".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "1.4".emphasis()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{}   ├─ OpenGL version: {}",
-            LogGenerator::timestamp().dimmed(),
-            gl_version.bright_white()
+            LogGenerator::timestamp().timestamp(),
+            gl_version.emphasis()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{}   ├─ Mesa driver: {}",
-            LogGenerator::timestamp().dimmed(),
-            "24.1.7".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "24.1.7".emphasis()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{}   ├─ GLSL version: {}",
-            LogGenerator::timestamp().dimmed(),
-            "4.60".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "4.60".emphasis()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{}   └─ Direct rendering: {}",
-            LogGenerator::timestamp().dimmed(),
-            "Yes".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "Yes".success()
         );
-        thread::sleep(Duration::from_millis(300));
+        clock::sleep(Duration::from_millis(300));
 
         Ok(())
     }
 
     fn probe_gpu(
         &self,
-        rng: &mut rand::rngs::ThreadRng,
+        rng: &mut SessionRng,
         exit_check: &dyn Fn() -> bool,
     ) -> io::Result<&'static str> {
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Initializing PCI bus enumeration...".bright_cyan()
+            LogGenerator::timestamp().timestamp(),
+            "Initializing PCI bus enumeration...".accent()
         );
-        thread::sleep(Duration::from_millis(rng.gen_range(300..600)));
+        clock::sleep(Duration::from_millis(rng.gen_range(300..600)));
 
         let gpu_configs = [
             (
@@ -147,63 +159,63 @@ impl XorgStage {
         let (gpu_name, driver, pci_slot, device_id, modes) =
             &gpu_configs[rng.gen_range(0..gpu_configs.len())];
 
-        println!(
+        outln!(
             "{}   └─ Scanning PCI device {}",
-            LogGenerator::timestamp().dimmed(),
-            pci_slot.bright_white()
+            LogGenerator::timestamp().timestamp(),
+            pci_slot.emphasis()
         );
-        thread::sleep(Duration::from_millis(250));
+        clock::sleep(Duration::from_millis(250));
 
-        println!(
+        outln!(
             "{}      └─ Device ID: {} {}",
-            LogGenerator::timestamp().dimmed(),
-            device_id.bright_white(),
-            "[VGA compatible controller]".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            device_id.emphasis(),
+            "[VGA compatible controller]".dim()
         );
-        thread::sleep(Duration::from_millis(200));
+        clock::sleep(Duration::from_millis(200));
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("  Detected: {}", gpu_name).bright_green().bold()
+            LogGenerator::timestamp().timestamp(),
+            format!("  Detected: {}", gpu_name).success().bold()
         );
-        thread::sleep(Duration::from_millis(300));
+        clock::sleep(Duration::from_millis(300));
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("Loading DRM/KMS driver: {}", driver).bright_cyan()
+            LogGenerator::timestamp().timestamp(),
+            format!("Loading DRM/KMS driver: {}", driver).accent()
         );
-        thread::sleep(Duration::from_millis(rng.gen_range(400..700)));
+        clock::sleep(Duration::from_millis(rng.gen_range(400..700)));
 
-        println!(
+        outln!(
             "{}   ├─ Initializing kernel mode setting (KMS)... {}",
-            LogGenerator::timestamp().dimmed(),
-            "[OK]".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "[OK]".success()
         );
-        thread::sleep(Duration::from_millis(300));
+        clock::sleep(Duration::from_millis(300));
 
-        println!(
+        outln!(
             "{}   ├─ Allocating framebuffer memory (256 MB)... {}",
-            LogGenerator::timestamp().dimmed(),
-            "[OK]".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "[OK]".success()
         );
-        thread::sleep(Duration::from_millis(250));
+        clock::sleep(Duration::from_millis(250));
 
-        println!(
+        outln!(
             "{}   ├─ Enabling DPMS (Display Power Management)... {}",
-            LogGenerator::timestamp().dimmed(),
-            "[OK]".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "[OK]".success()
         );
-        thread::sleep(Duration::from_millis(200));
+        clock::sleep(Duration::from_millis(200));
 
-        println!(
+        outln!(
             "{}   └─ GPU acceleration: {}",
-            LogGenerator::timestamp().dimmed(),
-            "Enabled".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "Enabled".success()
         );
-        thread::sleep(Duration::from_millis(300));
+        clock::sleep(Duration::from_millis(300));
 
         let vram = if driver == &"nvidia" || driver == &"amdgpu" {
             rng.gen_range(8..=16)
@@ -215,39 +227,39 @@ impl XorgStage {
         let vram_unit = if vram >= 1024 { "GB" } else { "MB" };
         let vram_display = if vram >= 1024 { vram / 1024 } else { vram };
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Querying video memory...".bright_cyan()
+            LogGenerator::timestamp().timestamp(),
+            "Querying video memory...".accent()
         );
-        thread::sleep(Duration::from_millis(400));
-        println!(
+        clock::sleep(Duration::from_millis(400));
+        outln!(
             "{}   ├─ Total VRAM: {} {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("{} {}", vram_display, vram_unit).bright_white(),
-            format!("(dedicated)").dimmed()
+            LogGenerator::timestamp().timestamp(),
+            format!("{} {}", vram_display, vram_unit).emphasis(),
+            "(dedicated)".dim()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{}   └─ Memory type: {}",
-            LogGenerator::timestamp().dimmed(),
+            LogGenerator::timestamp().timestamp(),
             if driver == &"nvidia" || driver == &"amdgpu" {
                 "GDDR6"
             } else {
                 "Shared"
             }
-            .bright_white()
+            .emphasis()
         );
-        thread::sleep(Duration::from_millis(250));
+        clock::sleep(Duration::from_millis(250));
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Enumerating display outputs...".bright_cyan()
+            LogGenerator::timestamp().timestamp(),
+            "Enumerating display outputs...".accent()
         );
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
 
         let outputs = if driver == &"nvidia" || driver == &"amdgpu" {
             vec!["DisplayPort-0", "HDMI-0", "DVI-D-0"]
@@ -264,14 +276,14 @@ impl XorgStage {
 
             let connected = i == 0;
             let status = if connected {
-                "Connected".bright_green()
+                "Connected".success()
             } else {
-                "Disconnected".dimmed()
+                "Disconnected".dim()
             };
-            println!(
+            outln!(
                 "{}   ├─ {}: {}",
-                LogGenerator::timestamp().dimmed(),
-                output.bright_white(),
+                LogGenerator::timestamp().timestamp(),
+                output.emphasis(),
                 status
             );
 
@@ -284,26 +296,26 @@ impl XorgStage {
                 } else {
                     60
                 };
-                thread::sleep(Duration::from_millis(200));
-                println!(
+                clock::sleep(Duration::from_millis(200));
+                outln!(
                     "{}   │  ├─ Preferred mode: {}@{}Hz",
-                    LogGenerator::timestamp().dimmed(),
-                    mode.bright_white(),
+                    LogGenerator::timestamp().timestamp(),
+                    mode.emphasis(),
                     refresh
                 );
-                println!(
+                outln!(
                     "{}   │  ├─ Color depth: {} {}",
-                    LogGenerator::timestamp().dimmed(),
-                    "24-bit".bright_white(),
-                    "(TrueColor)".dimmed()
+                    LogGenerator::timestamp().timestamp(),
+                    "24-bit".emphasis(),
+                    "(TrueColor)".dim()
                 );
-                println!(
+                outln!(
                     "{}   │  └─ EDID checksum: {}",
-                    LogGenerator::timestamp().dimmed(),
-                    "Valid".bright_green()
+                    LogGenerator::timestamp().timestamp(),
+                    "Valid".success()
                 );
             }
-            thread::sleep(Duration::from_millis(150));
+            clock::sleep(Duration::from_millis(150));
         }
 
         Ok(gpu_name)
@@ -311,16 +323,16 @@ impl XorgStage {
 
     fn load_extensions(
         &self,
-        rng: &mut rand::rngs::ThreadRng,
+        rng: &mut SessionRng,
         exit_check: &dyn Fn() -> bool,
     ) -> io::Result<()> {
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Loading X server extensions...".bright_cyan()
+            LogGenerator::timestamp().timestamp(),
+            "Loading X server extensions...".accent()
         );
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
         let extensions = [
             ("MIT-SHM", "Shared memory support"),
@@ -339,36 +351,32 @@ impl XorgStage {
             if exit_check() {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
-            print!(
+            out!(
                 "{}   ├─ {} ",
-                LogGenerator::timestamp().dimmed(),
-                ext.bright_white()
-            );
-            io::stdout().flush()?;
-            thread::sleep(Duration::from_millis(rng.gen_range(100..300)));
-            println!(
-                "{} {}",
-                "[LOADED]".bright_green(),
-                format!("({})", desc).dimmed()
+                LogGenerator::timestamp().timestamp(),
+                ext.emphasis()
             );
+            output::flush()?;
+            clock::sleep(Duration::from_millis(rng.gen_range(100..300)));
+            outln!("{} {}", "[LOADED]".success(), format!("({})", desc).dim());
         }
-        thread::sleep(Duration::from_millis(200));
+        clock::sleep(Duration::from_millis(200));
 
         Ok(())
     }
 
     fn detect_input_devices(
         &self,
-        rng: &mut rand::rngs::ThreadRng,
+        rng: &mut SessionRng,
         exit_check: &dyn Fn() -> bool,
     ) -> io::Result<()> {
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Detecting input devices...".bright_cyan()
+            LogGenerator::timestamp().timestamp(),
+            "Detecting input devices...".accent()
         );
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
 
         let devices = [
             ("AT Translated Set 2 keyboard", "event0", "keyboard"),
@@ -387,24 +395,24 @@ impl XorgStage {
             } else {
                 "├─"
             };
-            println!(
+            outln!(
                 "{}   {} /dev/input/{} → {}",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 tree_char,
-                event.bright_white(),
-                device.dimmed()
+                event.emphasis(),
+                device.dim()
             );
-            thread::sleep(Duration::from_millis(200));
+            clock::sleep(Duration::from_millis(200));
 
             let sub_tree_char = if i == devices.len() - 1 { " " } else { "│" };
-            println!(
+            outln!(
                 "{}   {}  └─ Driver: {} {}",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 sub_tree_char,
-                "libinput".bright_white(),
-                format!("[{}]", device_type).dimmed()
+                "libinput".emphasis(),
+                format!("[{}]", device_type).dim()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(150..300)));
+            clock::sleep(Duration::from_millis(rng.gen_range(150..300)));
         }
 
         Ok(())
@@ -412,24 +420,24 @@ impl XorgStage {
 }
 
 impl InstallationStage for XorgStage {
-    fn name(&self) -> &'static str {
-        "X Window System Setup"
+    fn info(&self) -> &'static StageInfo {
+        &Self::INFO
     }
 
     fn run(&self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
-        println!("\n{}", format!("> {}", self.name()).bright_yellow().bold());
-        println!();
+        outln!("\n{}", format!("> {}", self.name()).title().bold());
+        outln!();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = crate::rng::session();
         let mut spinner = Spinner::new();
 
-        println!(
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Installing X.Org Server packages...".bright_white().bold()
+            LogGenerator::timestamp().timestamp(),
+            "Installing X.Org Server packages...".emphasis().bold()
         );
-        thread::sleep(Duration::from_millis(400));
-        println!();
+        clock::sleep(Duration::from_millis(400));
+        outln!();
 
         let packages = [
             ("xserver-xorg-core", "1.21.1-7"),
@@ -450,30 +458,30 @@ impl InstallationStage for XorgStage {
             if exit_check() {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
-            println!(
+            outln!(
                 "{}   [+] {} {}",
-                LogGenerator::timestamp().dimmed(),
-                package.bright_white(),
-                format!("({})", version).dimmed()
+                LogGenerator::timestamp().timestamp(),
+                package.emphasis(),
+                format!("({})", version).dim()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(150..400)));
+            clock::sleep(Duration::from_millis(rng.gen_range(150..400)));
         }
 
-        println!();
+        outln!();
         spinner.animate(
             "Configuring X server security policies...",
             1200,
             exit_check,
         )?;
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "═══ Graphics Hardware Detection ═══".bright_yellow().bold()
+            LogGenerator::timestamp().timestamp(),
+            "═══ Graphics Hardware Detection ═══".header().bold()
         );
-        thread::sleep(Duration::from_millis(300));
-        println!();
+        clock::sleep(Duration::from_millis(300));
+        outln!();
 
         let _gpu_name = self.probe_gpu(&mut rng, exit_check)?;
 
@@ -482,42 +490,42 @@ impl InstallationStage for XorgStage {
         self.load_extensions(&mut rng, exit_check)?;
         self.detect_input_devices(&mut rng, exit_check)?;
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Configuring screen parameters...".bright_cyan()
+            LogGenerator::timestamp().timestamp(),
+            "Configuring screen parameters...".accent()
         );
-        thread::sleep(Duration::from_millis(500));
+        clock::sleep(Duration::from_millis(500));
 
         let dpi = rng.gen_range(90..=110);
-        println!(
+        outln!(
             "{}   ├─ Physical size: {} × {} mm",
-            LogGenerator::timestamp().dimmed(),
-            "508".bright_white(),
-            "285".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "508".emphasis(),
+            "285".emphasis()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{}   ├─ DPI: {}",
-            LogGenerator::timestamp().dimmed(),
-            format!("{} × {}", dpi, dpi).bright_white()
+            LogGenerator::timestamp().timestamp(),
+            format!("{} × {}", dpi, dpi).emphasis()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{}   └─ Virtual size: {}",
-            LogGenerator::timestamp().dimmed(),
-            "3840 × 2160".bright_white()
+            LogGenerator::timestamp().timestamp(),
+            "3840 × 2160".emphasis()
         );
-        thread::sleep(Duration::from_millis(300));
+        clock::sleep(Duration::from_millis(300));
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Scanning font directories...".bright_cyan()
+            LogGenerator::timestamp().timestamp(),
+            "Scanning font directories...".accent()
         );
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
         let font_dirs = [
             "/usr/share/fonts/X11/misc",
@@ -537,42 +545,42 @@ impl InstallationStage for XorgStage {
                 "├─"
             };
             let font_count = rng.gen_range(12..156);
-            println!(
+            outln!(
                 "{}   {} {} {} {}",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 tree_char,
-                dir.bright_white(),
-                format!("[{} fonts]", font_count).dimmed(),
-                "[OK]".bright_green()
+                dir.emphasis(),
+                format!("[{} fonts]", font_count).dim(),
+                "[OK]".success()
             );
-            thread::sleep(Duration::from_millis(rng.gen_range(100..250)));
+            clock::sleep(Duration::from_millis(rng.gen_range(100..250)));
         }
 
-        println!();
+        outln!();
         spinner.animate("Building font cache (fc-cache)...", 1800, exit_check)?;
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Loading cursor theme...".bright_cyan()
+            LogGenerator::timestamp().timestamp(),
+            "Loading cursor theme...".accent()
         );
-        thread::sleep(Duration::from_millis(400));
-        println!(
+        clock::sleep(Duration::from_millis(400));
+        outln!(
             "{}   └─ Theme: {} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Adwaita".bright_white(),
-            "(24px)".dimmed()
+            LogGenerator::timestamp().timestamp(),
+            "Adwaita".emphasis(),
+            "(24px)".dim()
         );
-        thread::sleep(Duration::from_millis(300));
+        clock::sleep(Duration::from_millis(300));
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Writing configuration files...".bright_cyan()
+            LogGenerator::timestamp().timestamp(),
+            "Writing configuration files...".accent()
         );
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
         let config_files = [
             "/etc/X11/xorg.conf",
@@ -591,100 +599,98 @@ impl InstallationStage for XorgStage {
             } else {
                 "├─"
             };
-            print!(
+            out!(
                 "{}   {} {} ",
-                LogGenerator::timestamp().dimmed(),
+                LogGenerator::timestamp().timestamp(),
                 tree_char,
-                file.bright_white()
+                file.emphasis()
             );
-            io::stdout().flush()?;
-            thread::sleep(Duration::from_millis(rng.gen_range(200..400)));
-            println!("{}", "[CREATED]".bright_green());
+            output::flush()?;
+            clock::sleep(Duration::from_millis(rng.gen_range(200..400)));
+            outln!("{}", "[CREATED]".success());
         }
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "Generating xorg.conf sections...".bright_cyan()
+            LogGenerator::timestamp().timestamp(),
+            "Generating xorg.conf sections...".accent()
         );
-        thread::sleep(Duration::from_millis(600));
+        clock::sleep(Duration::from_millis(600));
 
-        println!(
+        outln!(
             "{}   ├─ Section \"ServerLayout\" {}",
-            LogGenerator::timestamp().dimmed(),
-            "[OK]".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "[OK]".success()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{}   │  └─ Setting default screen to 0",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
-        thread::sleep(Duration::from_millis(150));
-        println!(
+        clock::sleep(Duration::from_millis(150));
+        outln!(
             "{}   ├─ Section \"InputDevice\" {}",
-            LogGenerator::timestamp().dimmed(),
-            "[OK]".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "[OK]".success()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{}   │  ├─ Keyboard: CoreKeyboard",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
-        thread::sleep(Duration::from_millis(150));
-        println!(
+        clock::sleep(Duration::from_millis(150));
+        outln!(
             "{}   │  └─ Pointer: CorePointer",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
-        thread::sleep(Duration::from_millis(150));
-        println!(
+        clock::sleep(Duration::from_millis(150));
+        outln!(
             "{}   ├─ Section \"Monitor\" {}",
-            LogGenerator::timestamp().dimmed(),
-            "[OK]".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "[OK]".success()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{}   │  └─ HorizSync: 30.0 - 83.0 kHz",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
-        thread::sleep(Duration::from_millis(150));
-        println!(
+        clock::sleep(Duration::from_millis(150));
+        outln!(
             "{}   ├─ Section \"Device\" {}",
-            LogGenerator::timestamp().dimmed(),
-            "[OK]".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "[OK]".success()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{}   │  ├─ Option \"AccelMethod\" \"sna\"",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
-        thread::sleep(Duration::from_millis(150));
-        println!(
+        clock::sleep(Duration::from_millis(150));
+        outln!(
             "{}   │  └─ Option \"TearFree\" \"true\"",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
-        thread::sleep(Duration::from_millis(150));
-        println!(
+        clock::sleep(Duration::from_millis(150));
+        outln!(
             "{}   └─ Section \"Screen\" {}",
-            LogGenerator::timestamp().dimmed(),
-            "[OK]".bright_green()
+            LogGenerator::timestamp().timestamp(),
+            "[OK]".success()
         );
-        thread::sleep(Duration::from_millis(200));
-        println!(
+        clock::sleep(Duration::from_millis(200));
+        outln!(
             "{}      └─ DefaultDepth: 24",
-            LogGenerator::timestamp().dimmed()
+            LogGenerator::timestamp().timestamp()
         );
-        thread::sleep(Duration::from_millis(300));
+        clock::sleep(Duration::from_millis(300));
 
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} {}",
-            LogGenerator::timestamp().dimmed(),
-            "X Window System configured successfully!"
-                .bright_green()
-                .bold()
+            LogGenerator::timestamp().timestamp(),
+            "X Window System configured successfully!".success().bold()
         );
-        thread::sleep(Duration::from_millis(400));
+        clock::sleep(Duration::from_millis(400));
 
         Ok(())
     }
@@ -0,0 +1,363 @@
+use crate::ui::SpinnerStyle;
+use clap::ValueEnum;
+use colored::{Color, ColoredString, Colorize};
+use std::sync::RwLock;
+
+/// What a piece of text means, so its color can come from the active theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Banners and separators around the installer and its cycles
+    Header,
+    /// The `> Stage name` line that opens each stage
+    Title,
+    /// Kernel-style `[    0.123456]` timestamps
+    Timestamp,
+    Success,
+    Warning,
+    Error,
+    /// Background noise: log details, hints, empty bar segments
+    Dim,
+    /// Messages that should stand out from the surrounding log
+    Emphasis,
+    /// Identifiers worth picking out: devices, images, package names
+    Accent,
+    /// Full-screen text-mode backdrops, like the blue Windows setup screen
+    Screen,
+    /// Text-mode dialog boxes drawn over the log
+    Panel,
+    /// Drop shadow beside a panel
+    Shadow,
+}
+
+/// How one role is drawn
+#[derive(Debug, Clone, Copy)]
+struct Paint {
+    color: Option<Color>,
+    background: Option<Color>,
+    dimmed: bool,
+    bold: bool,
+}
+
+const fn fg(color: Color) -> Paint {
+    Paint {
+        color: Some(color),
+        background: None,
+        dimmed: false,
+        bold: false,
+    }
+}
+
+const fn faint(color: Option<Color>) -> Paint {
+    Paint {
+        color,
+        background: None,
+        dimmed: true,
+        bold: false,
+    }
+}
+
+const fn strong(color: Color) -> Paint {
+    Paint {
+        color: Some(color),
+        background: None,
+        dimmed: false,
+        bold: true,
+    }
+}
+
+const fn on(color: Color, background: Color) -> Paint {
+    Paint {
+        color: Some(color),
+        background: Some(background),
+        dimmed: false,
+        bold: false,
+    }
+}
+
+/// A black drop shadow, whatever the theme's colors
+const SHADOW: Paint = Paint {
+    color: None,
+    background: Some(Color::Black),
+    dimmed: false,
+    bold: false,
+};
+
+const PLAIN: Paint = Paint {
+    color: None,
+    background: None,
+    dimmed: false,
+    bold: false,
+};
+
+/// Colors for every role
+#[derive(Debug)]
+pub struct Theme {
+    header: Paint,
+    title: Paint,
+    timestamp: Paint,
+    success: Paint,
+    warning: Paint,
+    error: Paint,
+    dim: Paint,
+    emphasis: Paint,
+    accent: Paint,
+    screen: Paint,
+    panel: Paint,
+    shadow: Paint,
+    /// Spinner used when neither the stage nor `--spinner` picks one
+    spinner: SpinnerStyle,
+}
+
+const AMBER: Color = Color::TrueColor {
+    r: 255,
+    g: 176,
+    b: 0,
+};
+const AMBER_BRIGHT: Color = Color::TrueColor {
+    r: 255,
+    g: 214,
+    b: 90,
+};
+const PHOSPHOR: Color = Color::TrueColor {
+    r: 51,
+    g: 230,
+    b: 51,
+};
+const PHOSPHOR_BRIGHT: Color = Color::TrueColor {
+    r: 160,
+    g: 255,
+    b: 160,
+};
+
+static DEFAULT: Theme = Theme {
+    header: fg(Color::BrightCyan),
+    title: fg(Color::BrightYellow),
+    timestamp: faint(None),
+    success: fg(Color::BrightGreen),
+    warning: fg(Color::Yellow),
+    error: fg(Color::BrightRed),
+    dim: faint(None),
+    emphasis: fg(Color::BrightWhite),
+    accent: fg(Color::Cyan),
+    screen: on(Color::BrightWhite, Color::Blue),
+    panel: on(Color::Black, Color::White),
+    shadow: SHADOW,
+    spinner: SpinnerStyle::Classic,
+};
+
+static AMBER_MONOCHROME: Theme = Theme {
+    header: fg(AMBER_BRIGHT),
+    title: fg(AMBER_BRIGHT),
+    timestamp: faint(Some(AMBER)),
+    success: fg(AMBER),
+    warning: fg(AMBER_BRIGHT),
+    error: strong(AMBER_BRIGHT),
+    dim: faint(Some(AMBER)),
+    emphasis: fg(AMBER_BRIGHT),
+    accent: fg(AMBER),
+    screen: on(Color::Black, AMBER),
+    panel: on(Color::Black, AMBER_BRIGHT),
+    shadow: SHADOW,
+    spinner: SpinnerStyle::Bounce,
+};
+
+static GREEN_PHOSPHOR: Theme = Theme {
+    header: fg(PHOSPHOR_BRIGHT),
+    title: fg(PHOSPHOR_BRIGHT),
+    timestamp: faint(Some(PHOSPHOR)),
+    success: fg(PHOSPHOR),
+    warning: fg(PHOSPHOR_BRIGHT),
+    error: strong(PHOSPHOR_BRIGHT),
+    dim: faint(Some(PHOSPHOR)),
+    emphasis: fg(PHOSPHOR_BRIGHT),
+    accent: fg(PHOSPHOR),
+    screen: on(Color::Black, PHOSPHOR),
+    panel: on(Color::Black, PHOSPHOR_BRIGHT),
+    shadow: SHADOW,
+    spinner: SpinnerStyle::Classic,
+};
+
+static IBM_BLUE: Theme = Theme {
+    header: fg(Color::BrightBlue),
+    title: fg(Color::BrightWhite),
+    timestamp: fg(Color::Blue),
+    success: fg(Color::BrightCyan),
+    warning: fg(Color::BrightYellow),
+    error: fg(Color::BrightRed),
+    dim: fg(Color::Blue),
+    emphasis: fg(Color::BrightWhite),
+    accent: fg(Color::BrightCyan),
+    screen: on(Color::BrightWhite, Color::Blue),
+    panel: on(Color::Black, Color::White),
+    shadow: SHADOW,
+    spinner: SpinnerStyle::Dos,
+};
+
+static HIGH_CONTRAST: Theme = Theme {
+    header: strong(Color::BrightWhite),
+    title: strong(Color::BrightYellow),
+    timestamp: fg(Color::White),
+    success: strong(Color::BrightGreen),
+    warning: strong(Color::BrightYellow),
+    error: strong(Color::BrightRed),
+    dim: fg(Color::White),
+    emphasis: strong(Color::BrightWhite),
+    accent: strong(Color::BrightCyan),
+    screen: on(Color::BrightWhite, Color::Blue),
+    panel: on(Color::Black, Color::BrightWhite),
+    shadow: SHADOW,
+    spinner: SpinnerStyle::Arrows,
+};
+
+static NO_COLOR: Theme = Theme {
+    header: PLAIN,
+    title: PLAIN,
+    timestamp: PLAIN,
+    success: PLAIN,
+    warning: PLAIN,
+    error: PLAIN,
+    dim: PLAIN,
+    emphasis: PLAIN,
+    accent: PLAIN,
+    screen: PLAIN,
+    panel: PLAIN,
+    shadow: PLAIN,
+    spinner: SpinnerStyle::Classic,
+};
+
+/// Built-in themes selectable with `--theme`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ThemeName {
+    /// The classic installer palette
+    #[default]
+    Default,
+    /// Amber monochrome monitor
+    Amber,
+    /// Green phosphor terminal
+    GreenPhosphor,
+    /// IBM blue and white
+    IbmBlue,
+    /// Bright, bold colors for readability
+    HighContrast,
+    /// No colors or text styles at all
+    NoColor,
+}
+
+impl ThemeName {
+    fn theme(self) -> &'static Theme {
+        match self {
+            ThemeName::Default => &DEFAULT,
+            ThemeName::Amber => &AMBER_MONOCHROME,
+            ThemeName::GreenPhosphor => &GREEN_PHOSPHOR,
+            ThemeName::IbmBlue => &IBM_BLUE,
+            ThemeName::HighContrast => &HIGH_CONTRAST,
+            ThemeName::NoColor => &NO_COLOR,
+        }
+    }
+}
+
+static ACTIVE: RwLock<&Theme> = RwLock::new(&DEFAULT);
+
+/// Switch every role to the colors of the named theme
+pub fn set_theme(name: ThemeName) {
+    *ACTIVE.write().unwrap() = name.theme();
+    if name == ThemeName::NoColor {
+        colored::control::set_override(false);
+    }
+}
+
+/// Spinner style the active theme prefers
+pub fn spinner_style() -> SpinnerStyle {
+    ACTIVE.read().unwrap().spinner
+}
+
+impl Theme {
+    fn paint(&self, role: Role) -> Paint {
+        match role {
+            Role::Header => self.header,
+            Role::Title => self.title,
+            Role::Timestamp => self.timestamp,
+            Role::Success => self.success,
+            Role::Warning => self.warning,
+            Role::Error => self.error,
+            Role::Dim => self.dim,
+            Role::Emphasis => self.emphasis,
+            Role::Accent => self.accent,
+            Role::Screen => self.screen,
+            Role::Panel => self.panel,
+            Role::Shadow => self.shadow,
+        }
+    }
+}
+
+/// Style text by its role in the active theme, in the same chaining style as `colored`
+pub trait Themed: Into<ColoredString> {
+    fn role(self, role: Role) -> ColoredString {
+        let paint = ACTIVE.read().unwrap().paint(role);
+        let mut text: ColoredString = self.into();
+        if let Some(color) = paint.color {
+            text = text.color(color);
+        }
+        if let Some(background) = paint.background {
+            text = text.on_color(background);
+        }
+        if paint.dimmed {
+            text = text.dimmed();
+        }
+        if paint.bold {
+            text = text.bold();
+        }
+        text
+    }
+
+    fn header(self) -> ColoredString {
+        self.role(Role::Header)
+    }
+
+    fn title(self) -> ColoredString {
+        self.role(Role::Title)
+    }
+
+    fn timestamp(self) -> ColoredString {
+        self.role(Role::Timestamp)
+    }
+
+    fn success(self) -> ColoredString {
+        self.role(Role::Success)
+    }
+
+    fn warning(self) -> ColoredString {
+        self.role(Role::Warning)
+    }
+
+    fn error(self) -> ColoredString {
+        self.role(Role::Error)
+    }
+
+    fn dim(self) -> ColoredString {
+        self.role(Role::Dim)
+    }
+
+    fn emphasis(self) -> ColoredString {
+        self.role(Role::Emphasis)
+    }
+
+    fn accent(self) -> ColoredString {
+        self.role(Role::Accent)
+    }
+
+    fn screen(self) -> ColoredString {
+        self.role(Role::Screen)
+    }
+
+    fn panel(self) -> ColoredString {
+        self.role(Role::Panel)
+    }
+
+    fn shadow(self) -> ColoredString {
+        self.role(Role::Shadow)
+    }
+}
+
+impl Themed for &str {}
+impl Themed for ColoredString {}
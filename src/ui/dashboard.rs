@@ -0,0 +1,252 @@
+use super::status::{hms, RunStatus};
+use super::{ProgressBar, ProgressStyle};
+use crate::theme::Themed;
+use colored::*;
+use crossterm::{
+    cursor, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+use std::collections::VecDeque;
+
+/// Lines kept in the log pane's scrollback
+const LOG_CAPACITY: usize = 500;
+
+/// Rows used by the header, stage line, gauge and separators above the log pane
+const TOP_ROWS: usize = 5;
+
+/// Rows used by the separator and footer below the log pane
+const BOTTOM_ROWS: usize = 2;
+
+/// Full-screen view that turns the installer's output stream into a fixed layout:
+/// header, current stage, overall gauge, a scrolling log pane and a footer
+pub struct Dashboard {
+    run: RunStatus,
+    /// Log pane contents; the last line is the one still being written
+    log: VecDeque<String>,
+    /// Line the installer output is currently writing to
+    row: usize,
+    size: (u16, u16),
+    /// Shown in the footer in place of the key hints, e.g. while paused
+    notice: Option<String>,
+    previous: Vec<String>,
+}
+
+impl Dashboard {
+    pub fn new() -> Self {
+        Self {
+            run: RunStatus::new(),
+            log: VecDeque::from([String::new()]),
+            row: 0,
+            size: (0, 0),
+            notice: None,
+            previous: Vec::new(),
+        }
+    }
+
+    pub fn run_status(&mut self) -> &mut RunStatus {
+        &mut self.run
+    }
+
+    pub fn set_notice(&mut self, notice: Option<String>) {
+        self.notice = notice;
+    }
+
+    /// Forget what is on screen so the next render redraws every row
+    pub fn invalidate(&mut self) {
+        self.previous.clear();
+    }
+
+    /// Feed installer output into the log pane, honoring `\n`, `\r` and cursor-up redraws
+    pub fn feed(&mut self, text: &str) {
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\n' => {
+                    self.row += 1;
+                    if self.row == self.log.len() {
+                        self.log.push_back(String::new());
+                    }
+                    if self.log.len() > LOG_CAPACITY {
+                        self.log.pop_front();
+                        self.row -= 1;
+                    }
+                }
+                '\r' => self.log[self.row].clear(),
+                '\x1b' => {
+                    let mut sequence = String::from(c);
+                    for c in chars.by_ref() {
+                        sequence.push(c);
+                        if c.is_ascii_alphabetic() {
+                            break;
+                        }
+                    }
+                    self.escape(&sequence);
+                }
+                _ => self.log[self.row].push(c),
+            }
+        }
+    }
+
+    /// Apply an escape sequence: colors are kept, cursor-up moves between log lines,
+    /// and anything else is dropped since the dashboard owns the screen
+    fn escape(&mut self, sequence: &str) {
+        let Some(params) = sequence.strip_prefix("\x1b[") else {
+            return;
+        };
+        if params.ends_with('m') {
+            self.log[self.row].push_str(sequence);
+        } else if let Some(count) = params.strip_suffix('A') {
+            let count = count.parse().unwrap_or(1);
+            self.row = self.row.saturating_sub(count);
+        }
+    }
+
+    fn rows(&mut self, width: usize, height: usize) -> Vec<String> {
+        let mut rows = Vec::with_capacity(height);
+
+        let title = " UNIVERSAL SYSTEM INSTALLER v3.2.1 (Build 1999)";
+        let cycle = format!("Cycle #{} ", self.run.cycle());
+        let gap = width.saturating_sub(title.chars().count() + cycle.chars().count());
+        rows.push(
+            format!("{}{}{}", title, " ".repeat(gap), cycle)
+                .header()
+                .reversed()
+                .bold()
+                .to_string(),
+        );
+        rows.push("═".repeat(width).header().to_string());
+
+        let stage = self.run.stage_label();
+        rows.push(format!(" {}", stage.title().bold()));
+
+        let gauge = ProgressBar::new(ProgressStyle::Block).with_width(width.saturating_sub(18));
+        rows.push(format!(
+            " {} {}",
+            "Overall".emphasis(),
+            gauge.render(self.run.progress())
+        ));
+        rows.push("─".repeat(width).dim().to_string());
+
+        let pane = height.saturating_sub(TOP_ROWS + BOTTOM_ROWS);
+        let mut lines: Vec<&String> = self.log.iter().collect();
+        if lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let skip = lines.len().saturating_sub(pane);
+        rows.extend(lines[skip..].iter().map(|line| line.to_string()));
+        rows.resize(TOP_ROWS + pane, String::new());
+
+        rows.push("─".repeat(width).dim().to_string());
+        let hint = self
+            .notice
+            .as_deref()
+            .unwrap_or("space pause  s skip  +/- speed  q quit");
+        let footer = format!(
+            " Elapsed {}  |  ETA {}  |  {}",
+            hms(self.run.elapsed()),
+            hms(self.run.eta()),
+            hint.trim()
+        );
+        let padding = " ".repeat(width.saturating_sub(footer.chars().count()));
+        rows.push(
+            format!("{}{}", footer, padding)
+                .emphasis()
+                .reversed()
+                .to_string(),
+        );
+
+        rows.truncate(height);
+        rows
+    }
+
+    /// Produce the terminal output needed to bring the screen up to date,
+    /// rewriting only the rows that changed since the last frame
+    pub fn render(&mut self) -> String {
+        let size = terminal::size().unwrap_or((80, 24));
+        let mut frame = Vec::new();
+        if size != self.size {
+            self.size = size;
+            self.previous.clear();
+            let _ = queue!(frame, terminal::Clear(ClearType::All));
+        }
+
+        let (width, height) = (size.0 as usize, size.1 as usize);
+        let rows = self.rows(width, height);
+        for (index, row) in rows.iter().enumerate() {
+            if self.previous.get(index) == Some(row) {
+                continue;
+            }
+            let _ = queue!(
+                frame,
+                cursor::MoveTo(0, index as u16),
+                Print(fit(row, width)),
+                terminal::Clear(ClearType::UntilNewLine)
+            );
+        }
+        self.previous = rows;
+
+        String::from_utf8_lossy(&frame).into_owned()
+    }
+}
+
+impl Default for Dashboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cut a line down to `width` visible columns, keeping ANSI color sequences intact
+fn fit(line: &str, width: usize) -> String {
+    let mut fitted = String::with_capacity(line.len());
+    let mut visible = 0;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            fitted.push(c);
+            if chars.peek() == Some(&'[') {
+                for c in chars.by_ref() {
+                    fitted.push(c);
+                    if ('@'..='~').contains(&c) && c != '[' {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        if visible == width {
+            continue;
+        }
+        fitted.push(c);
+        visible += 1;
+    }
+
+    fitted.push_str("\x1b[0m");
+    fitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_handles_redraws() {
+        let mut dashboard = Dashboard::new();
+        dashboard.feed("first\nspinner |\rspinner /\rdone\nlast");
+        assert_eq!(dashboard.log, ["first", "done", "last"]);
+    }
+
+    #[test]
+    fn test_feed_moves_cursor_up() {
+        let mut dashboard = Dashboard::new();
+        dashboard.feed("a 10%\nb 20%\n\x1b[2A\ra 50%\x1b[K\n\rb 60%\n");
+        assert_eq!(dashboard.log, ["a 50%", "b 60%", ""]);
+    }
+
+    #[test]
+    fn test_fit_ignores_color_codes() {
+        let line = "\x1b[32mok\x1b[0m done";
+        assert_eq!(fit(line, 4), "\x1b[32mok\x1b[0m d\x1b[0m");
+    }
+}
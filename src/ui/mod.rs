@@ -1,4 +1,93 @@
+mod dashboard;
+mod multi;
+mod pager;
 pub mod progress;
+mod prompt;
 mod spinner;
-pub use progress::{ProgressBar, ProgressStyle};
-pub use spinner::Spinner;
+mod status;
+pub use dashboard::Dashboard;
+pub use multi::MultiProgress;
+pub use pager::Pager;
+pub use progress::{ProgressBar, ProgressCurve, ProgressStyle};
+pub use prompt::{
+    prompt_timeout, set_prompt_timeout, set_unattended, unattended, Answer, Prompt,
+    UnattendedPolicy,
+};
+pub use spinner::{set_spinner_style, Spinner, SpinnerStyle};
+pub use status::{RunStatus, StatusLine};
+
+use crate::output;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+static DASHBOARD: AtomicBool = AtomicBool::new(false);
+static STATUS_LINE: AtomicBool = AtomicBool::new(false);
+
+/// Switch to pipe-friendly output: no colors and no `\r` redraws
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+    if plain {
+        colored::control::set_override(false);
+    }
+}
+
+/// Whether output is plain, line-oriented text
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Run inside the full-screen dashboard instead of a scrolling log
+pub fn set_dashboard(dashboard: bool) {
+    DASHBOARD.store(dashboard, Ordering::Relaxed);
+}
+
+pub fn is_dashboard() -> bool {
+    DASHBOARD.load(Ordering::Relaxed)
+}
+
+/// Keep a status line pinned to the bottom of the terminal
+pub fn set_status_line(status_line: bool) {
+    STATUS_LINE.store(status_line, Ordering::Relaxed);
+}
+
+pub fn is_status_line() -> bool {
+    STATUS_LINE.load(Ordering::Relaxed)
+}
+
+/// Return to the start of the line so it can be overwritten, or start a new line in plain mode
+pub fn rewind_line() -> io::Result<()> {
+    if is_plain() {
+        outln!();
+    } else {
+        out!("\r");
+    }
+    output::flush()
+}
+
+/// Redraw an in-place status line; in plain mode only each quarter of progress is printed
+pub fn redraw_line(line: &str, step: u64, steps: u64) -> io::Result<()> {
+    if is_plain() {
+        let steps = steps.max(1);
+        if step > 0 && step * 4 / steps != (step - 1) * 4 / steps {
+            outln!("{}", line);
+        }
+    } else {
+        out!("\r{}", line);
+    }
+    output::flush()
+}
+
+/// Number of terminal columns `text` occupies, ignoring ANSI color sequences
+pub fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.find(|c| c.is_ascii_alphabetic());
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
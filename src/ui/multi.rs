@@ -0,0 +1,160 @@
+use super::progress::Tracker;
+use super::ProgressBar;
+use crate::clock;
+use crate::output;
+use crate::theme::Themed;
+use crossterm::{
+    cursor, execute,
+    terminal::{self, ClearType},
+};
+use std::io;
+use std::time::Duration;
+
+/// Simulated time between redraws of the whole group
+const TICK_MS: u64 = 100;
+
+/// Steps in each bar's progress curve
+const CURVE_STEPS: u64 = 40;
+
+struct Task {
+    label: String,
+    duration_ms: u64,
+    bar: ProgressBar,
+    path: Vec<f32>,
+    tracker: Tracker,
+}
+
+impl Task {
+    /// Progress on this task's curve after `elapsed_ms`
+    fn progress(&self, elapsed_ms: u64) -> f32 {
+        if elapsed_ms >= self.duration_ms {
+            return 1.0;
+        }
+        let step = elapsed_ms * CURVE_STEPS / self.duration_ms.max(1);
+        self.path[step as usize]
+    }
+}
+
+/// Several progress bars running at once, each finishing in its own time,
+/// like `docker pull` layers or parallel `make -j` jobs
+#[derive(Default)]
+pub struct MultiProgress {
+    tasks: Vec<Task>,
+}
+
+impl MultiProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a bar labeled `label` that completes after `duration_ms`
+    pub fn add(&mut self, label: impl Into<String>, duration_ms: u64, bar: ProgressBar) {
+        let path = bar.path(CURVE_STEPS);
+        let tracker = bar.tracker(duration_ms);
+        self.tasks.push(Task {
+            label: label.into(),
+            duration_ms,
+            bar,
+            path,
+            tracker,
+        });
+    }
+
+    /// Number of bars added so far
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Animate every bar until the slowest one is done, redrawing them in place
+    pub fn animate(mut self, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        // Pad labels so the bars line up in a column
+        let label_width = self
+            .tasks
+            .iter()
+            .map(|task| super::visible_width(&task.label))
+            .max()
+            .unwrap_or(0);
+        for task in &mut self.tasks {
+            let padding = label_width - super::visible_width(&task.label);
+            task.label.push_str(&" ".repeat(padding));
+            let width = task.bar.fitted_width(&task.label);
+            task.bar = task.bar.clone().with_width(width);
+        }
+
+        let total_ms = self
+            .tasks
+            .iter()
+            .map(|task| task.duration_ms)
+            .max()
+            .unwrap_or(0);
+        let mut finished = vec![false; self.tasks.len()];
+        let mut elapsed_ms = 0;
+
+        loop {
+            if exit_check() {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
+            }
+
+            if super::is_plain() {
+                // Plain output can't redraw, so each bar is printed once when it completes
+                for (task, done) in self.tasks.iter_mut().zip(&mut finished) {
+                    if !*done && elapsed_ms >= task.duration_ms {
+                        *done = true;
+                        let elapsed_secs = task.duration_ms as f32 / 1000.0;
+                        let status = task.bar.status(&mut task.tracker, 1.0, elapsed_secs);
+                        outln!("{} {}", task.label, status);
+                    }
+                }
+            } else {
+                if elapsed_ms > 0 {
+                    execute!(output::stdout(), cursor::MoveUp(self.tasks.len() as u16))?;
+                }
+                for task in &mut self.tasks {
+                    let progress = task.progress(elapsed_ms);
+                    let elapsed_secs = elapsed_ms.min(task.duration_ms) as f32 / 1000.0;
+                    let status = task.bar.status(&mut task.tracker, progress, elapsed_secs);
+                    out!("\r{} {}", task.label.emphasis(), status);
+                    execute!(output::stdout(), terminal::Clear(ClearType::UntilNewLine))?;
+                    outln!();
+                }
+                output::flush()?;
+            }
+
+            if elapsed_ms >= total_ms {
+                break;
+            }
+            clock::sleep(Duration::from_millis(TICK_MS));
+            elapsed_ms += TICK_MS;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::ProgressStyle;
+
+    #[test]
+    fn test_progress_stays_on_the_curve() {
+        let mut group = MultiProgress::new();
+        group.add("layer", 1000, ProgressBar::new(ProgressStyle::Hash));
+        group.add("instant", 0, ProgressBar::new(ProgressStyle::Hash));
+        let task = &group.tasks[0];
+
+        assert_eq!(task.path.len() as u64, CURVE_STEPS + 1);
+        assert_eq!(task.progress(0), task.path[0]);
+        assert_eq!(task.progress(999), task.path[CURVE_STEPS as usize - 1]);
+        assert_eq!(task.progress(1000), 1.0);
+        assert_eq!(group.tasks[1].progress(0), 1.0);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::clock;
+use crate::input;
+use crate::output;
+use crate::theme::Themed;
+use colored::*;
+use crossterm::{cursor, event::KeyCode, queue, terminal};
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Widest the pager box gets, borders included
+const MAX_WIDTH: usize = 78;
+
+/// Pause between lines when the pager scrolls by itself
+const AUTO_SCROLL_STEP: Duration = Duration::from_millis(120);
+
+/// A bordered box of text scrolled with the keyboard, ending in a button that
+/// can only be pressed once the text has been scrolled to the end
+pub struct Pager {
+    title: String,
+    text: String,
+}
+
+impl Pager {
+    pub fn new(title: &str, text: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    /// Page through the text until `button` is pressed with ENTER. Nobody at the
+    /// keyboard, or nobody pressing anything for a prompt timeout, makes it
+    /// scroll to the end and press the button by itself
+    pub fn accept(&self, button: &str, exit_check: &dyn Fn() -> bool) -> io::Result<()> {
+        let (columns, rows) = terminal::size().unwrap_or((80, 24));
+        let width = (columns as usize).clamp(24, MAX_WIDTH) - 4;
+        let height = (rows as usize).saturating_sub(12).clamp(4, 16);
+        let mut view = View {
+            lines: wrap(&self.text, width),
+            top: 0,
+            width,
+            height,
+        };
+        let last = view.lines.len().saturating_sub(height);
+
+        if super::is_plain() {
+            // Nothing to scroll with, so the whole text goes in one tall box
+            let whole = View {
+                height: view.lines.len(),
+                ..view
+            };
+            for row in self.frame(&whole) {
+                outln!("{}", row);
+            }
+            outln!(
+                "  {} {}",
+                format!("[ {} ]", button).success(),
+                "(unattended)".dim()
+            );
+            return Ok(());
+        }
+
+        input::discard_keys();
+        let auto = super::unattended().is_some();
+        let idle = super::prompt_timeout().map(clock::scale);
+        let mut read = last == 0;
+        let mut last_key = Instant::now();
+
+        self.draw(&view, button, read, false)?;
+        loop {
+            if exit_check() {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
+            }
+
+            let previous = view.top;
+            let key = input::take_key();
+            if key.is_some() {
+                last_key = Instant::now();
+            }
+            match key {
+                Some(KeyCode::PageDown) => view.top = (view.top + height).min(last),
+                Some(KeyCode::PageUp) => view.top = view.top.saturating_sub(height),
+                Some(KeyCode::Down) => view.top = (view.top + 1).min(last),
+                Some(KeyCode::Up) => view.top = view.top.saturating_sub(1),
+                Some(KeyCode::Enter) if read => break,
+                _ => {}
+            }
+
+            let waiting = auto || idle.is_some_and(|idle| last_key.elapsed() >= idle);
+            if waiting && key.is_none() {
+                if view.top == last && read {
+                    clock::sleep(AUTO_SCROLL_STEP * 5);
+                    break;
+                }
+                view.top = (view.top + 1).min(last);
+                clock::sleep(AUTO_SCROLL_STEP);
+            } else {
+                thread::sleep(Duration::from_millis(20));
+            }
+
+            if view.top != previous || (view.top == last && !read) {
+                read |= view.top == last;
+                self.draw(&view, button, read, true)?;
+            }
+        }
+
+        // Settle the button row on the pressed button
+        let note = if auto { "(unattended)" } else { "" };
+        out!(
+            "\r  {} {}\x1b[K",
+            format!("[ {} ]", button).success().bold(),
+            note.dim()
+        );
+        outln!();
+        output::flush()
+    }
+
+    /// Box rows for the lines in view
+    fn frame(&self, view: &View) -> Vec<String> {
+        let lines = &view.lines;
+        let (top, width, height) = (view.top, view.width, view.height);
+        let title = format!(" {} ", self.title);
+        let fill = (width + 2).saturating_sub(title.chars().count());
+        let mut rows = vec![format!(
+            "{}{}{}",
+            "┌─".dim(),
+            title.title().bold(),
+            format!("{}┐", "─".repeat(fill.saturating_sub(1))).dim()
+        )];
+        for index in top..top + height {
+            let line = lines.get(index).map(String::as_str).unwrap_or("");
+            rows.push(format!(
+                "{} {:<width$} {}",
+                "│".dim(),
+                line,
+                "│".dim(),
+                width = width
+            ));
+        }
+
+        let end = (top + height).min(lines.len());
+        let position = format!(" {}-{} of {} ", top + 1, end, lines.len());
+        let fill = (width + 2).saturating_sub(position.chars().count());
+        rows.push(format!(
+            "{}",
+            format!("└{}{}─┘", "─".repeat(fill.saturating_sub(1)), position).dim()
+        ));
+        rows
+    }
+
+    fn draw(&self, view: &View, button: &str, enabled: bool, redraw: bool) -> io::Result<()> {
+        let mut out = output::stdout();
+        if redraw {
+            // Back over the box to its top border
+            queue!(out, cursor::MoveUp(view.height as u16 + 2))?;
+        }
+        for row in self.frame(view) {
+            out!("\r{}\n", row);
+        }
+
+        let button = format!("[ {} ]", button);
+        if enabled {
+            out!(
+                "\r  {} {}\x1b[K",
+                button.success().bold(),
+                "Press ENTER to accept".dim()
+            );
+        } else {
+            out!(
+                "\r  {} {}\x1b[K",
+                button.dim(),
+                "Scroll to the end with PgDn to continue".dim()
+            );
+        }
+        out.flush()
+    }
+}
+
+/// The wrapped text and which part of it is on screen
+struct View {
+    lines: Vec<String>,
+    /// First line shown
+    top: usize,
+    width: usize,
+    height: usize,
+}
+
+/// Reflow paragraphs to `width` columns; blank lines separate paragraphs
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split("\n\n") {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_keeps_words_and_paragraphs() {
+        let lines = wrap("one two three\nfour\n\nfive", 9);
+        assert_eq!(lines, ["one two", "three", "four", "", "five"]);
+    }
+}
@@ -1,74 +1,366 @@
-use colored::*;
-use std::io::{self, Write};
-use std::thread;
+use crate::clock;
+use crate::output;
+use crate::rng;
+use crate::theme::Themed;
+use rand::Rng;
+use serde::Deserialize;
+use std::io;
 use std::time::Duration;
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ProgressStyle {
     Hash,
     Equals,
     Block,
-    #[allow(dead_code)]
     Gradient,
+    /// Solid blocks with a ░▒▓ shaded leading edge
+    Dos,
 }
 
+/// How progress advances over the course of an animation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressCurve {
+    /// Steady progress from start to finish
+    #[default]
+    Linear,
+    /// Races to 99% and then sits there for the rest of the time
+    Stall,
+    /// Long pauses broken up by sudden jumps
+    Jump,
+    /// Mostly steady, but occasionally slips backwards
+    Regress,
+}
+
+impl ProgressCurve {
+    /// Progress after each of `steps` equally timed steps, from 0.0 to exactly 1.0
+    pub fn path<R: Rng>(self, steps: u64, rng: &mut R) -> Vec<f32> {
+        let steps = steps.max(1);
+        let linear = |i: u64| i as f32 / steps as f32;
+
+        let mut path: Vec<f32> = match self {
+            ProgressCurve::Linear => (0..=steps).map(linear).collect(),
+            ProgressCurve::Stall => {
+                let rush = (steps * 7 / 10).max(1);
+                (0..=steps)
+                    .map(|i| (i as f32 / rush as f32).min(1.0) * 0.99)
+                    .collect()
+            }
+            ProgressCurve::Jump => {
+                let increments: Vec<f32> = (0..steps)
+                    .map(|_| {
+                        if rng.gen_bool(0.7) {
+                            0.0
+                        } else {
+                            rng.gen_range(0.5..3.0)
+                        }
+                    })
+                    .collect();
+                let total: f32 = increments.iter().sum::<f32>().max(f32::EPSILON);
+                let mut done = 0.0;
+                std::iter::once(0.0)
+                    .chain(increments.iter().map(|increment| {
+                        done += increment / total;
+                        done
+                    }))
+                    .collect()
+            }
+            ProgressCurve::Regress => {
+                let mut setback = 0.0;
+                (0..=steps)
+                    .map(|i| {
+                        setback *= 0.7;
+                        if i > 0 && rng.gen_bool(0.08) {
+                            setback += rng.gen_range(0.02..0.08);
+                        }
+                        (linear(i) - setback).max(0.0)
+                    })
+                    .collect()
+            }
+        };
+
+        if let Some(last) = path.last_mut() {
+            *last = 1.0;
+        }
+        path
+    }
+}
+
+/// Total size and nominal speed of a simulated transfer
+#[derive(Debug, Clone, Copy)]
+struct Transfer {
+    total_bytes: u64,
+    bytes_per_sec: u64,
+}
+
+#[derive(Debug, Clone)]
 pub struct ProgressBar {
-    width: usize,
+    /// Fixed bar width; when unset the bar fills the space left on the terminal line
+    width: Option<usize>,
     style: ProgressStyle,
+    curve: ProgressCurve,
+    transfer: Option<Transfer>,
+    show_eta: bool,
 }
 
 impl ProgressBar {
     pub fn new(style: ProgressStyle) -> Self {
-        Self { width: 20, style }
+        Self {
+            width: None,
+            style,
+            curve: ProgressCurve::Linear,
+            transfer: None,
+            show_eta: false,
+        }
+    }
+
+    /// Use a bar `width` characters wide instead of adapting to the terminal
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn with_curve(mut self, curve: ProgressCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Show a transfer-rate column for a download of `total_bytes` at roughly `bytes_per_sec`
+    pub fn with_transfer(mut self, total_bytes: u64, bytes_per_sec: u64) -> Self {
+        self.transfer = Some(Transfer {
+            total_bytes,
+            bytes_per_sec: bytes_per_sec.max(1),
+        });
+        self
+    }
+
+    /// Show an estimated-time-remaining column
+    pub fn with_eta(mut self) -> Self {
+        self.show_eta = true;
+        self
     }
 
     pub fn render(&self, progress: f32) -> String {
-        let filled = ((progress * self.width as f32) as usize).min(self.width);
-        let empty = self.width - filled;
+        if super::is_plain() {
+            return format!("{:3.0}%", progress * 100.0);
+        }
+
+        let width = self.width.unwrap_or(20);
+        let cells = (progress * width as f32).clamp(0.0, width as f32);
+        let filled = cells as usize;
 
-        let (fill_char, empty_char) = match self.style {
-            ProgressStyle::Hash => ('#', '.'),
-            ProgressStyle::Equals => ('=', ' '),
-            ProgressStyle::Block => ('█', '░'),
+        let (bar, empty_char) = match self.style {
+            ProgressStyle::Hash => ("#".repeat(filled), '.'),
+            ProgressStyle::Equals => ("=".repeat(filled), ' '),
+            ProgressStyle::Block => ("█".repeat(filled), '░'),
             ProgressStyle::Gradient => {
-                if filled > empty {
-                    ('▓', '░')
-                } else {
-                    ('▒', '░')
+                let fill = if filled * 2 > width { "▓" } else { "▒" };
+                (fill.repeat(filled), '░')
+            }
+            ProgressStyle::Dos => {
+                // Shade the leading cell to show progress between whole cells
+                let mut bar = "█".repeat(filled);
+                if filled < width {
+                    bar.push_str(match cells.fract() {
+                        f if f >= 2.0 / 3.0 => "▓",
+                        f if f >= 1.0 / 3.0 => "▒",
+                        f if f > 0.0 => "░",
+                        _ => "",
+                    });
                 }
+                (bar, ' ')
             }
         };
+        let empty = width - bar.chars().count();
 
         format!(
             "[{}{}] {:3.0}%",
-            fill_char.to_string().repeat(filled).bright_green(),
-            empty_char.to_string().repeat(empty).dimmed(),
+            bar.success(),
+            empty_char.to_string().repeat(empty).dim(),
             progress * 100.0
         )
     }
 
+    /// Bar width that fits next to `message` and the extra columns on one terminal line
+    pub(super) fn fitted_width(&self, message: &str) -> usize {
+        if let Some(width) = self.width {
+            return width;
+        }
+
+        let (columns, _) = crossterm::terminal::size().unwrap_or((80, 24));
+        let mut reserved = super::visible_width(message) + " [] 100%".len();
+        if self.transfer.is_some() {
+            reserved += " 999.9MB/s".len();
+        }
+        if self.show_eta {
+            reserved += " ETA 00:00:00".len();
+        }
+        (columns as usize)
+            .saturating_sub(reserved + 1)
+            .clamp(10, 40)
+    }
+
+    /// This bar's progress after each of `steps` steps, following its curve
+    pub(super) fn path(&self, steps: u64) -> Vec<f32> {
+        self.curve.path(steps, &mut rng::session())
+    }
+
+    /// Start rate and ETA bookkeeping for a run lasting `duration_ms`
+    pub(super) fn tracker(&self, duration_ms: u64) -> Tracker {
+        Tracker {
+            duration_secs: duration_ms as f32 / 1000.0,
+            elapsed_secs: 0.0,
+            progress: 0.0,
+            rate: self.transfer.map_or(0.0, |t| t.bytes_per_sec as f32),
+        }
+    }
+
+    /// Render the bar plus its rate and ETA columns after `elapsed_secs` of simulated time
+    pub(super) fn status(&self, tracker: &mut Tracker, progress: f32, elapsed_secs: f32) -> String {
+        let mut status = self.render(progress);
+        match self.transfer {
+            Some(transfer) => {
+                // Smoothed across updates so jumps and stalls read like a real download
+                let elapsed = elapsed_secs - tracker.elapsed_secs;
+                if elapsed > 0.0 {
+                    let advanced = (progress - tracker.progress).max(0.0);
+                    let expected = elapsed / tracker.duration_secs.max(f32::EPSILON);
+                    let instant = transfer.bytes_per_sec as f32 * advanced / expected;
+                    tracker.rate = tracker.rate * 0.7 + instant * 0.3;
+                }
+                status.push_str(&format!(" {:>9}", format_rate(tracker.rate)));
+                if self.show_eta {
+                    let remaining = transfer.total_bytes as f32 * (1.0 - progress);
+                    let eta = (tracker.rate >= 1.0).then(|| remaining / tracker.rate);
+                    status.push_str(&format!(" ETA {}", format_eta(eta)));
+                }
+            }
+            None if self.show_eta => {
+                let eta = (progress > 0.0).then(|| elapsed_secs * (1.0 - progress) / progress);
+                status.push_str(&format!(" ETA {}", format_eta(eta)));
+            }
+            None => {}
+        }
+        tracker.elapsed_secs = elapsed_secs;
+        tracker.progress = progress;
+        status
+    }
+
     pub fn animate(
         &self,
         message: &str,
         duration_ms: u64,
         exit_check: &dyn Fn() -> bool,
     ) -> io::Result<()> {
-        let steps = 50;
+        let steps = 40;
         let delay = duration_ms / steps;
-        print!("{}", message.bright_white());
-        io::stdout().flush()?;
+        let bar = self.clone().with_width(self.fitted_width(message));
+        let path = self.path(steps);
+        let mut tracker = self.tracker(duration_ms);
+        if !super::is_plain() {
+            out!("{}", message.emphasis());
+            output::flush()?;
+        }
 
-        for i in 0..=steps {
+        for (i, &progress) in path.iter().enumerate() {
             if exit_check() {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
 
-            let progress = i as f32 / steps as f32;
-            print!("\r{} {}", message.bright_white(), self.render(progress));
-            io::stdout().flush()?;
-            thread::sleep(Duration::from_millis(delay));
+            let elapsed_secs = (i as u64 * delay) as f32 / 1000.0;
+            super::redraw_line(
+                &format!(
+                    "{} {}",
+                    message.emphasis(),
+                    bar.status(&mut tracker, progress, elapsed_secs)
+                ),
+                i as u64,
+                steps,
+            )?;
+            clock::sleep(Duration::from_millis(delay));
+        }
+        if !super::is_plain() {
+            outln!();
         }
-        println!();
         Ok(())
     }
 }
+
+/// Rate and ETA state for a bar that is being animated
+pub(super) struct Tracker {
+    duration_secs: f32,
+    elapsed_secs: f32,
+    progress: f32,
+    rate: f32,
+}
+
+/// Format a transfer rate such as `12.4MB/s`
+fn format_rate(bytes_per_sec: f32) -> String {
+    const UNITS: [&str; 4] = ["B/s", "KB/s", "MB/s", "GB/s"];
+    let mut value = bytes_per_sec.max(0.0);
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0}{}", value, UNITS[unit])
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
+
+/// Format a remaining time as `MM:SS`, or `H:MM:SS` past an hour; unknown shows `--:--`
+fn format_eta(secs: Option<f32>) -> String {
+    match secs {
+        Some(secs) if secs.is_finite() && secs < 360_000.0 => {
+            let secs = secs.round() as u64;
+            if secs >= 3600 {
+                format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+            } else {
+                format!("{:02}:{:02}", secs / 60, secs % 60)
+            }
+        }
+        _ => "--:--".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_curves_run_from_zero_to_one() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for curve in [
+            ProgressCurve::Linear,
+            ProgressCurve::Stall,
+            ProgressCurve::Jump,
+            ProgressCurve::Regress,
+        ] {
+            let path = curve.path(40, &mut rng);
+            assert_eq!(path.len(), 41, "{:?}", curve);
+            assert_eq!(path[0], 0.0, "{:?}", curve);
+            assert_eq!(path[40], 1.0, "{:?}", curve);
+            assert!(path.iter().all(|p| (0.0..=1.0).contains(p)), "{:?}", curve);
+        }
+    }
+
+    #[test]
+    fn test_stall_holds_at_99_percent() {
+        let path = ProgressCurve::Stall.path(40, &mut StdRng::seed_from_u64(1));
+        assert!(path[28..40].iter().all(|&p| p == 0.99));
+    }
+
+    #[test]
+    fn test_format_rate_and_eta() {
+        assert_eq!(format_rate(12.4 * 1024.0 * 1024.0), "12.4MB/s");
+        assert_eq!(format_rate(512.0), "512B/s");
+        assert_eq!(format_eta(Some(37.0)), "00:37");
+        assert_eq!(format_eta(Some(3725.0)), "1:02:05");
+        assert_eq!(format_eta(None), "--:--");
+    }
+}
@@ -0,0 +1,310 @@
+use crate::input;
+use crate::output;
+use crate::theme::Themed;
+use clap::ValueEnum;
+use colored::*;
+use crossterm::event::KeyCode;
+use rand::Rng;
+use std::io;
+use std::sync::RwLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long an unattended prompt "thinks" before answering itself
+const UNATTENDED_PAUSE: Duration = Duration::from_millis(800);
+
+/// Longest a routine confirmation holds up the installation
+const BRIEF_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How prompts answer themselves when nobody is at the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum UnattendedPolicy {
+    /// Take each prompt's default answer
+    Defaults,
+    /// Agree to everything and pick the first choice
+    Yes,
+    /// Answer at random
+    Random,
+}
+
+/// Policy chosen with `--unattended`
+static UNATTENDED: RwLock<Option<UnattendedPolicy>> = RwLock::new(None);
+
+/// How long prompts wait for a key when they don't set their own timeout
+static TIMEOUT: RwLock<Option<Duration>> = RwLock::new(Some(Duration::from_secs(15)));
+
+/// Answer prompts automatically, or `None` to wait for the keyboard
+pub fn set_unattended(policy: Option<UnattendedPolicy>) {
+    *UNATTENDED.write().unwrap() = policy;
+}
+
+/// The policy prompts answer by; plain output has no keyboard, so it always takes defaults
+pub fn unattended() -> Option<UnattendedPolicy> {
+    let policy = *UNATTENDED.read().unwrap();
+    policy.or(super::is_plain().then_some(UnattendedPolicy::Defaults))
+}
+
+/// Default prompt timeout; `None` waits for as long as it takes
+pub fn set_prompt_timeout(timeout: Option<Duration>) {
+    *TIMEOUT.write().unwrap() = timeout;
+}
+
+/// How long prompts wait for a key by default
+pub fn prompt_timeout() -> Option<Duration> {
+    *TIMEOUT.read().unwrap()
+}
+
+/// What a prompt was answered with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    Yes,
+    No,
+    /// Index into the choices, from zero
+    Choice(usize),
+    /// A "press any key" or "press ENTER" prompt was dismissed
+    Continue,
+}
+
+impl Answer {
+    pub fn is_yes(self) -> bool {
+        self == Answer::Yes
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    YesNo,
+    Choice(Vec<String>),
+    AnyKey,
+    Enter,
+}
+
+/// A question waiting on the keyboard, which answers itself with its default
+/// when it times out or when running unattended
+pub struct Prompt {
+    question: String,
+    kind: Kind,
+    default: Answer,
+    timeout: Option<Duration>,
+}
+
+impl Prompt {
+    /// `Question? [Y/n]`, with the capital letter marking the default
+    pub fn yes_no(question: &str, default: bool) -> Self {
+        Self::new(
+            question,
+            Kind::YesNo,
+            if default { Answer::Yes } else { Answer::No },
+        )
+    }
+
+    /// A numbered list of choices, answered with the number of one
+    pub fn choice(question: &str, choices: &[&str], default: usize) -> Self {
+        let choices = choices.iter().map(|choice| choice.to_string()).collect();
+        Self::new(question, Kind::Choice(choices), Answer::Choice(default))
+    }
+
+    /// `Press any key to continue`, or whatever the question says
+    pub fn any_key(question: &str) -> Self {
+        Self::new(question, Kind::AnyKey, Answer::Continue)
+    }
+
+    /// `Press ENTER to continue`, ignoring every other key
+    pub fn enter(question: &str) -> Self {
+        Self::new(question, Kind::Enter, Answer::Continue)
+    }
+
+    fn new(question: &str, kind: Kind, default: Answer) -> Self {
+        Self {
+            question: question.to_string(),
+            kind,
+            default,
+            timeout: prompt_timeout(),
+        }
+    }
+
+    /// Give up waiting after `timeout` and take the default answer
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Time out quickly, for confirmations that would only stall a run nobody is watching
+    pub fn brief(mut self) -> Self {
+        self.timeout = Some(
+            self.timeout
+                .map_or(BRIEF_TIMEOUT, |timeout| timeout.min(BRIEF_TIMEOUT)),
+        );
+        self
+    }
+
+    fn hint(&self) -> String {
+        match (&self.kind, self.default) {
+            (Kind::YesNo, Answer::Yes) => "[Y/n]".to_string(),
+            (Kind::YesNo, _) => "[y/N]".to_string(),
+            (Kind::Choice(choices), _) => format!("[1-{}]", choices.len()),
+            (Kind::AnyKey | Kind::Enter, _) => String::new(),
+        }
+    }
+
+    /// What the answer looks like when typed
+    fn echo(answer: Answer) -> String {
+        match answer {
+            Answer::Yes => "y".to_string(),
+            Answer::No => "n".to_string(),
+            Answer::Choice(index) => (index + 1).to_string(),
+            Answer::Continue => String::new(),
+        }
+    }
+
+    /// The answer a key stands for, if it is one
+    fn parse(&self, key: KeyCode) -> Option<Answer> {
+        match (&self.kind, key) {
+            (Kind::AnyKey, _) => Some(Answer::Continue),
+            (_, KeyCode::Enter) => Some(self.default),
+            (Kind::YesNo, KeyCode::Char('y' | 'Y')) => Some(Answer::Yes),
+            (Kind::YesNo, KeyCode::Char('n' | 'N')) => Some(Answer::No),
+            (Kind::Choice(choices), KeyCode::Char(c)) => c
+                .to_digit(10)
+                .map(|digit| digit as usize)
+                .filter(|&digit| (1..=choices.len()).contains(&digit))
+                .map(|digit| Answer::Choice(digit - 1)),
+            _ => None,
+        }
+    }
+
+    fn auto_answer(&self, policy: UnattendedPolicy) -> Answer {
+        match (policy, &self.kind) {
+            (UnattendedPolicy::Defaults, _) | (_, Kind::AnyKey | Kind::Enter) => self.default,
+            (UnattendedPolicy::Yes, Kind::YesNo) => Answer::Yes,
+            (UnattendedPolicy::Yes, Kind::Choice(_)) => Answer::Choice(0),
+            (UnattendedPolicy::Random, Kind::YesNo) => {
+                if crate::rng::session().gen_bool(0.5) {
+                    Answer::Yes
+                } else {
+                    Answer::No
+                }
+            }
+            (UnattendedPolicy::Random, Kind::Choice(choices)) => {
+                Answer::Choice(crate::rng::session().gen_range(0..choices.len()))
+            }
+        }
+    }
+
+    fn line(&self) -> String {
+        let hint = self.hint();
+        if hint.is_empty() {
+            format!("{} ", self.question.warning().bold())
+        } else {
+            format!("{} {} ", self.question.warning().bold(), hint.emphasis())
+        }
+    }
+
+    /// Show the prompt and wait for an answer
+    pub fn ask(&self, exit_check: &dyn Fn() -> bool) -> io::Result<Answer> {
+        if let Kind::Choice(choices) = &self.kind {
+            for (index, choice) in choices.iter().enumerate() {
+                outln!("  {}) {}", index + 1, choice);
+            }
+        }
+        out!("{}", self.line());
+        output::flush()?;
+
+        if let Some(policy) = unattended() {
+            crate::clock::sleep(UNATTENDED_PAUSE);
+            if exit_check() {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
+            }
+            let answer = self.auto_answer(policy);
+            self.finish(answer, Some("(unattended)"))?;
+            return Ok(answer);
+        }
+
+        input::discard_keys();
+        let deadline = self
+            .timeout
+            .map(|timeout| Instant::now() + crate::clock::scale(timeout));
+        let mut shown = None;
+        loop {
+            if exit_check() {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
+            }
+            if let Some(answer) = input::take_key().and_then(|key| self.parse(key)) {
+                self.finish(answer, None)?;
+                return Ok(answer);
+            }
+
+            if let Some(deadline) = deadline {
+                let left = deadline.saturating_duration_since(Instant::now());
+                if left.is_zero() {
+                    self.finish(self.default, Some("(no response)"))?;
+                    return Ok(self.default);
+                }
+                let seconds = left.as_secs() + 1;
+                if shown != Some(seconds) {
+                    shown = Some(seconds);
+                    out!("\r{}{}", self.line(), format!("({}s) ", seconds).dim());
+                    output::flush()?;
+                }
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    /// Settle the prompt line on its answer
+    fn finish(&self, answer: Answer, note: Option<&str>) -> io::Result<()> {
+        let mut line = Self::echo(answer);
+        if let Some(note) = note {
+            line = format!("{} {}", line, note.dim()).trim_start().to_string();
+        }
+        if super::is_plain() {
+            outln!("{}", line);
+        } else {
+            // Overwrite the countdown, which may be wider than the answer
+            out!("\r{}{}\x1b[K", self.line(), line);
+            outln!();
+        }
+        output::flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enter_takes_the_default() {
+        let prompt = Prompt::yes_no("Continue?", false);
+        assert_eq!(prompt.hint(), "[y/N]");
+        assert_eq!(prompt.parse(KeyCode::Enter), Some(Answer::No));
+        assert_eq!(prompt.parse(KeyCode::Char('Y')), Some(Answer::Yes));
+        assert_eq!(prompt.parse(KeyCode::Char('x')), None);
+    }
+
+    #[test]
+    fn test_choices_are_numbered_from_one() {
+        let prompt = Prompt::choice("Pick one", &["Retry", "Abort"], 0);
+        assert_eq!(prompt.hint(), "[1-2]");
+        assert_eq!(prompt.parse(KeyCode::Char('2')), Some(Answer::Choice(1)));
+        assert_eq!(prompt.parse(KeyCode::Char('3')), None);
+        assert_eq!(prompt.parse(KeyCode::Char('0')), None);
+    }
+
+    #[test]
+    fn test_enter_prompt_ignores_other_keys() {
+        let prompt = Prompt::enter("Waiting for ENTER...");
+        assert_eq!(prompt.parse(KeyCode::Enter), Some(Answer::Continue));
+        assert_eq!(prompt.parse(KeyCode::Char('y')), None);
+        assert_eq!(prompt.parse(KeyCode::Esc), None);
+        let prompt = Prompt::any_key("Press any key to continue");
+        assert_eq!(prompt.parse(KeyCode::Esc), Some(Answer::Continue));
+    }
+
+    #[test]
+    fn test_brief_caps_the_timeout() {
+        let prompt = Prompt::yes_no("Continue?", true).timeout(Duration::from_secs(60));
+        assert_eq!(prompt.brief().timeout, Some(BRIEF_TIMEOUT));
+        let prompt = Prompt::yes_no("Continue?", true).timeout(Duration::from_secs(1));
+        assert_eq!(prompt.brief().timeout, Some(Duration::from_secs(1)));
+    }
+}
@@ -1,25 +1,92 @@
-use colored::*;
-use std::io::{self, Write};
-use std::thread;
+use crate::clock;
+use crate::output;
+use crate::theme::{self, Themed};
+use clap::ValueEnum;
+use crossterm::{
+    execute,
+    terminal::{self, ClearType},
+};
+use serde::Deserialize;
+use std::io;
+use std::sync::RwLock;
 use std::time::Duration;
 
+/// Animation frames a spinner cycles through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpinnerStyle {
+    /// | / - \
+    Classic,
+    /// Rotating braille dots
+    Braille,
+    /// A block bouncing inside a bar
+    Bounce,
+    /// Clock faces ticking round
+    Clock,
+    /// Arrows pointing round the compass
+    Arrows,
+    /// DOS shading blocks
+    Dos,
+}
+
+impl SpinnerStyle {
+    pub fn frames(self) -> &'static [&'static str] {
+        match self {
+            SpinnerStyle::Classic => &["|", "/", "-", "\\"],
+            SpinnerStyle::Braille => &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+            SpinnerStyle::Bounce => &["[=   ]", "[ =  ]", "[  = ]", "[   =]", "[  = ]", "[ =  ]"],
+            SpinnerStyle::Clock => &[
+                "🕛", "🕐", "🕑", "🕒", "🕓", "🕔", "🕕", "🕖", "🕗", "🕘", "🕙", "🕚",
+            ],
+            SpinnerStyle::Arrows => &["←", "↖", "↑", "↗", "→", "↘", "↓", "↙"],
+            SpinnerStyle::Dos => &["░", "▒", "▓", "█", "▓", "▒"],
+        }
+    }
+}
+
+/// Style chosen with `--spinner`; it wins over stage and theme preferences
+static OVERRIDE: RwLock<Option<SpinnerStyle>> = RwLock::new(None);
+
+/// Force every spinner to one style, or `None` to let stages and the theme decide
+pub fn set_spinner_style(style: Option<SpinnerStyle>) {
+    *OVERRIDE.write().unwrap() = style;
+}
+
 pub struct Spinner {
-    chars: Vec<char>,
+    style: Option<SpinnerStyle>,
     index: usize,
 }
 
 impl Spinner {
     pub fn new() -> Self {
         Self {
-            chars: vec!['|', '/', '-', '\\'],
+            style: None,
             index: 0,
         }
     }
 
-    pub fn next(&mut self) -> char {
-        let c = self.chars[self.index];
-        self.index = (self.index + 1) % self.chars.len();
-        c
+    /// Prefer `style` over the theme's spinner, unless `--spinner` says otherwise
+    pub fn with_style(style: SpinnerStyle) -> Self {
+        Self {
+            style: Some(style),
+            index: 0,
+        }
+    }
+
+    fn frames(&self) -> &'static [&'static str] {
+        let style = OVERRIDE
+            .read()
+            .unwrap()
+            .or(self.style)
+            .unwrap_or_else(theme::spinner_style);
+        style.frames()
+    }
+
+    pub fn next(&mut self) -> &'static str {
+        let frames = self.frames();
+        let frame = frames[self.index % frames.len()];
+        self.index = (self.index + 1) % frames.len();
+        frame
     }
 
     pub fn animate(
@@ -34,11 +101,20 @@ impl Spinner {
                 return Err(io::Error::new(io::ErrorKind::Interrupted, "User interrupt"));
             }
 
-            print!("\r{} {}", self.next(), message.bright_white());
-            io::stdout().flush()?;
-            thread::sleep(Duration::from_millis(100));
+            if !super::is_plain() {
+                out!("\r{} {}", self.next(), message.emphasis());
+                output::flush()?;
+            }
+            clock::sleep(Duration::from_millis(100));
+        }
+        if super::is_plain() {
+            outln!("  {}", message);
+        } else {
+            // Wider frames leave residue behind the shorter final line
+            out!("\r  {}", message.emphasis());
+            execute!(output::stdout(), terminal::Clear(ClearType::UntilNewLine))?;
+            outln!();
         }
-        print!("\r  {}\n", message.bright_white());
         Ok(())
     }
 }
@@ -48,3 +124,20 @@ impl Default for Spinner {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_share_a_width() {
+        for style in SpinnerStyle::value_variants() {
+            let widths: Vec<usize> = style
+                .frames()
+                .iter()
+                .map(|frame| frame.chars().count())
+                .collect();
+            assert!(widths.windows(2).all(|w| w[0] == w[1]), "{:?}", style);
+        }
+    }
+}
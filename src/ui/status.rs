@@ -0,0 +1,278 @@
+use crate::clock;
+use crate::stages::StageInfo;
+use crate::theme::Themed;
+use colored::*;
+use crossterm::{
+    cursor, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+/// How long an ETA holds before it is recalculated
+const ETA_REFRESH: Duration = Duration::from_secs(3);
+
+/// Shortest gap between status line redraws
+const REDRAW_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Where the run is: cycle, stage and timings, as shown by the dashboard and the status line
+pub struct RunStatus {
+    started: Instant,
+    cycle: u32,
+    stages: Vec<&'static StageInfo>,
+    stage_index: usize,
+    stage_started: Instant,
+    /// Last ETA shown and when it was worked out
+    eta: Option<(Duration, Instant)>,
+}
+
+impl RunStatus {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            cycle: 1,
+            stages: Vec::new(),
+            stage_index: 0,
+            stage_started: Instant::now(),
+            eta: None,
+        }
+    }
+
+    /// Begin a new installation cycle over the given stages
+    pub fn start_cycle(&mut self, cycle: u32, stages: Vec<&'static StageInfo>) {
+        self.cycle = cycle;
+        self.stages = stages;
+        self.stage_index = 0;
+        self.stage_started = Instant::now();
+        self.eta = None;
+    }
+
+    /// Mark the stage at `index` of the current cycle as running
+    pub fn start_stage(&mut self, index: usize) {
+        self.stage_index = index;
+        self.stage_started = Instant::now();
+        self.eta = None;
+    }
+
+    pub fn cycle(&self) -> u32 {
+        self.cycle
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// `Stage 7/20: Name`, or a placeholder before the first stage starts
+    pub fn stage_label(&self) -> String {
+        match self.stages.get(self.stage_index) {
+            Some(info) => format!(
+                "Stage {}/{}: {}",
+                self.stage_index + 1,
+                self.stages.len(),
+                info.name
+            ),
+            None => "Preparing installation...".to_string(),
+        }
+    }
+
+    /// Overall progress through the cycle, estimating the current stage from its expected duration
+    pub fn progress(&self) -> f32 {
+        if self.stages.is_empty() {
+            return 0.0;
+        }
+
+        let expected = clock::scale(self.stages[self.stage_index].estimated_duration);
+        let within = if expected.is_zero() {
+            0.0
+        } else {
+            (self.stage_started.elapsed().as_secs_f32() / expected.as_secs_f32()).min(0.95)
+        };
+        (self.stage_index as f32 + within) / self.stages.len() as f32
+    }
+
+    /// Remaining time for the cycle; it counts down for a while, then gets
+    /// recalculated and jumps the way installer estimates always do
+    pub fn eta(&mut self) -> Duration {
+        if let Some((eta, at)) = self.eta {
+            if at.elapsed() < ETA_REFRESH {
+                return eta.saturating_sub(at.elapsed());
+            }
+        }
+
+        let total: Duration = self
+            .stages
+            .iter()
+            .map(|info| clock::scale(info.estimated_duration))
+            .sum();
+        let remaining = total.mul_f32(1.0 - self.progress());
+        // Not the session generator, so seeded runs replay the same way with or without it
+        let mut rng = rand::thread_rng();
+        let factor = match rng.gen_range(0..10) {
+            0 => rng.gen_range(3.0..8.0),
+            1..=2 => rng.gen_range(0.2..0.6),
+            _ => rng.gen_range(0.8..1.4),
+        };
+        let eta = remaining.mul_f32(factor);
+        self.eta = Some((eta, Instant::now()));
+        eta
+    }
+}
+
+impl Default for RunStatus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A line pinned to the bottom row of the terminal, below a scroll region
+/// that the rest of the output scrolls inside
+pub struct StatusLine {
+    run: RunStatus,
+    rows: u16,
+    /// Last line drawn and when
+    drawn: Option<(String, Instant)>,
+}
+
+impl StatusLine {
+    pub fn new() -> Self {
+        Self {
+            run: RunStatus::new(),
+            rows: 0,
+            drawn: None,
+        }
+    }
+
+    pub fn run_status(&mut self) -> &mut RunStatus {
+        &mut self.run
+    }
+
+    /// Output that reserves the bottom row, keeping the cursor where it was
+    pub fn setup(&mut self) -> String {
+        let (_, rows) = terminal::size().unwrap_or((80, 24));
+        self.rows = rows;
+        // Scroll once first, so the cursor can't end up on the reserved row
+        format!("\n\x1b[1A{}", self.restore())
+    }
+
+    /// Output that sets the scroll region up again after something else reset it,
+    /// keeping the cursor where it was
+    pub fn restore(&mut self) -> String {
+        self.drawn = None;
+        format!("\x1b7\x1b[1;{}r\x1b8", self.rows.saturating_sub(1).max(1))
+    }
+
+    /// Output that hands the whole screen back and clears the status line
+    pub fn teardown(&self) -> String {
+        let mut teardown = Vec::new();
+        let _ = queue!(
+            teardown,
+            Print("\x1b7\x1b[r"),
+            cursor::MoveTo(0, self.rows.saturating_sub(1)),
+            terminal::Clear(ClearType::CurrentLine),
+            Print("\x1b8")
+        );
+        String::from_utf8_lossy(&teardown).into_owned()
+    }
+
+    /// Output that redraws the status line, or nothing if it was drawn moments ago
+    /// and `force` is not set
+    pub fn render(&mut self, force: bool) -> String {
+        if !force
+            && self
+                .drawn
+                .as_ref()
+                .is_some_and(|(_, at)| at.elapsed() < REDRAW_INTERVAL)
+        {
+            return String::new();
+        }
+
+        let (width, rows) = terminal::size().unwrap_or((80, 24));
+        let mut frame = String::new();
+        if rows != self.rows {
+            frame = self.setup();
+        }
+
+        let line = format!(
+            " Cycle #{}  |  {}  |  Elapsed {}  |  ETA {}",
+            self.run.cycle(),
+            self.run.stage_label(),
+            hms(self.run.elapsed()),
+            hms(self.run.eta())
+        );
+        let line: String = line.chars().take(width as usize).collect();
+        if !force && frame.is_empty() && self.drawn.as_ref().is_some_and(|(last, _)| *last == line)
+        {
+            return String::new();
+        }
+
+        let padding = " ".repeat((width as usize).saturating_sub(line.chars().count()));
+        let mut draw = Vec::new();
+        let _ = queue!(
+            draw,
+            Print("\x1b7"),
+            cursor::MoveTo(0, self.rows.saturating_sub(1)),
+            Print(format!("{}{}", line, padding).emphasis().reversed()),
+            Print("\x1b8")
+        );
+        frame.push_str(&String::from_utf8_lossy(&draw));
+        self.drawn = Some((line, Instant::now()));
+        frame
+    }
+}
+
+impl Default for StatusLine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Format a duration as `HH:MM:SS`
+pub(super) fn hms(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static A: StageInfo = StageInfo {
+        id: "a",
+        name: "Alpha",
+        description: "",
+        category: crate::stages::Category::System,
+        estimated_duration: Duration::from_secs(10),
+        tags: &[],
+    };
+
+    #[test]
+    fn test_stage_label_counts_from_one() {
+        let mut run = RunStatus::new();
+        assert_eq!(run.stage_label(), "Preparing installation...");
+        run.start_cycle(2, vec![&A, &A, &A]);
+        run.start_stage(1);
+        assert_eq!(run.stage_label(), "Stage 2/3: Alpha");
+    }
+
+    #[test]
+    fn test_eta_holds_between_refreshes() {
+        clock::set_thread_speed(1.0);
+        let mut run = RunStatus::new();
+        run.start_cycle(1, vec![&A, &A, &A]);
+        let first = run.eta();
+        assert!(first > Duration::ZERO);
+        let (_, worked_out) = run.eta.unwrap();
+
+        // Within the refresh window it only counts down from the same estimate
+        let held = run.eta();
+        assert!(held <= first);
+        assert!(first - held <= worked_out.elapsed());
+        assert_eq!(run.eta.unwrap().1, worked_out);
+
+        // Once the window is over it gets worked out again
+        run.eta = Some((first, worked_out - ETA_REFRESH));
+        run.eta();
+        assert!(run.eta.unwrap().1 > worked_out);
+    }
+}